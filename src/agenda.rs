@@ -21,20 +21,19 @@ macro_rules! create {
         let component = crate::widgets::tasks::Model::builder()
             .launch(().into())
             .forward($sender.output_sender(), std::convert::identity);
-        component
-            .widget()
-            .set_vscrollbar_policy(gtk::PolicyType::Never);
+        component.emit(crate::widgets::tasks::MsgInput::DisableScroll);
 
         component
     }};
 }
 
 macro_rules! update {
-    ($self:ident, $exp:expr, $task:ident, $get:ident, $list:ident, $date:ident) => {{
+    ($self:ident, $exp:expr, $task:ident, $get:ident, $list:ident, $date:ident, $title:literal, $capacity:expr) => {{
         use relm4::ComponentController as _;
 
         let tasks = $self.$get(&$list, $date);
 
+        $exp.set_label(Some(&Self::label($title, &tasks, $capacity)));
         $exp.set_expanded(!tasks.is_empty());
         $exp.set_sensitive(!tasks.is_empty());
         $self
@@ -56,19 +55,70 @@ impl Model {
     fn update_tasks(&self, widgets: &ModelWidgets) {
         let list = crate::application::tasks();
         let date = crate::date::from_glib(widgets.calendar.date());
+        let capacity = crate::application::preferences().capacity;
 
-        update!(self, widgets.past_exp, past, past_tasks, list, date);
-        update!(self, widgets.today_exp, today, today_tasks, list, date);
+        update!(
+            self,
+            widgets.past_exp,
+            past,
+            past_tasks,
+            list,
+            date,
+            "Past due",
+            None
+        );
+        update!(
+            self,
+            widgets.today_exp,
+            today,
+            today_tasks,
+            list,
+            date,
+            "Today",
+            capacity
+        );
         update!(
             self,
             widgets.tomorrow_exp,
             tomorrow,
             tomorrow_tasks,
             list,
-            date
+            date,
+            "Tomorrow",
+            capacity
+        );
+        update!(
+            self,
+            widgets.week_exp,
+            week,
+            week_tasks,
+            list,
+            date,
+            "This week",
+            capacity
+        );
+        update!(
+            self,
+            widgets.month_exp,
+            month,
+            month_tasks,
+            list,
+            date,
+            "This month",
+            capacity
         );
-        update!(self, widgets.week_exp, week, week_tasks, list, date);
-        update!(self, widgets.month_exp, month, month_tasks, list, date);
+    }
+
+    /// Section title with its estimated work.
+    fn label(
+        title: &str,
+        tasks: &[crate::tasks::Task],
+        capacity: Option<chrono::Duration>,
+    ) -> String {
+        match crate::tasks::estimate::summary(tasks, capacity) {
+            Some(summary) => format!("{title} · {summary}"),
+            None => title.to_string(),
+        }
     }
 
     fn past_tasks(
@@ -154,6 +204,48 @@ impl Model {
         tasks
     }
 
+    pub fn title(&self) -> String {
+        format!("Agenda {}", self.date.format("%Y-%m-%d"))
+    }
+
+    /// Non-empty sections for the selected date, sorted as displayed.
+    pub fn sections(&self) -> Vec<(String, Vec<crate::tasks::Task>)> {
+        let list = crate::application::tasks();
+        let date = self.date;
+
+        let mut sections = vec![
+            ("Past due", self.past_tasks(&list, date)),
+            ("Today", self.today_tasks(&list, date)),
+            ("Tomorrow", self.tomorrow_tasks(&list, date)),
+            ("This week", self.week_tasks(&list, date)),
+            ("This month", self.month_tasks(&list, date)),
+        ];
+
+        for (title, tasks) in &mut sections {
+            if *title == "Today" {
+                tasks.sort_by(Self::by_time);
+            } else {
+                tasks.sort();
+                tasks.reverse();
+            }
+        }
+
+        sections
+            .into_iter()
+            .filter(|(_, tasks)| !tasks.is_empty())
+            .map(|(title, tasks)| (title.to_string(), tasks))
+            .collect()
+    }
+
+    fn by_time(a: &crate::tasks::Task, b: &crate::tasks::Task) -> std::cmp::Ordering {
+        match (a.due_time(), b.due_time()) {
+            (Some(x), Some(y)) if x != y => x.cmp(&y),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            _ => b.cmp(a),
+        }
+    }
+
     fn update_marks(&self, widgets: &ModelWidgets) {
         use chrono::Datelike as _;
 
@@ -199,9 +291,32 @@ impl relm4::Component for Model {
             week: create!(sender),
         };
 
+        model
+            .today
+            .emit(crate::widgets::tasks::MsgInput::SortBy(Self::by_time));
+
         let widgets = view_output!();
         sender.input(Msg::DateSelect(init));
 
+        // Tasks dropped on the today or tomorrow section are due that day
+        for (expander, days) in [(&widgets.today_exp, 0), (&widgets.tomorrow_exp, 1)] {
+            let calendar = widgets.calendar.clone();
+            let sender = sender.clone();
+
+            expander.add_controller(crate::widgets::task::task_drop_target(
+                move |id, _, _, _| {
+                    let date =
+                        crate::date::from_glib(calendar.date()) + chrono::Duration::days(days);
+                    let action = crate::tasks::bulk::Action::Due(Some(date));
+
+                    sender
+                        .output(crate::widgets::task::MsgOutput::Bulk(vec![id], action))
+                        .ok();
+                    true
+                },
+            ));
+        }
+
         relm4::ComponentParts { model, widgets }
     }
 
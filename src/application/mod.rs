@@ -11,17 +11,25 @@ use relm4::ComponentController as _;
 use relm4::prelude::*;
 
 pub const NAME: &str = env!("CARGO_PKG_NAME");
+const ADD_HINT: &str = "Call Bob tomorrow at 3pm !A +work @phone every monday";
+const CAPACITY_HINT: &str = "Estimated work per day above which the agenda warns";
+/// Daily capacity when the preference is empty.
+const DEFAULT_CAPACITY: chrono::Duration = chrono::Duration::hours(8);
 
 #[derive(Clone, Copy, Debug)]
 #[repr(u32)]
 enum Page {
     Inbox = 0,
+    Next,
+    Today,
     Projects,
     Contexts,
     Tags,
     Agenda,
     Flag,
     Done,
+    Dashboard,
+    Review,
     Search,
 }
 
@@ -29,13 +37,17 @@ impl From<u32> for Page {
     fn from(n: u32) -> Self {
         match n {
             0 => Page::Inbox,
-            1 => Page::Projects,
-            2 => Page::Contexts,
-            3 => Page::Tags,
-            4 => Page::Agenda,
-            5 => Page::Flag,
-            6 => Page::Done,
-            7 => Page::Search,
+            1 => Page::Next,
+            2 => Page::Today,
+            3 => Page::Projects,
+            4 => Page::Contexts,
+            5 => Page::Tags,
+            6 => Page::Agenda,
+            7 => Page::Flag,
+            8 => Page::Done,
+            9 => Page::Dashboard,
+            10 => Page::Review,
+            11 => Page::Search,
             _ => panic!("Invalid page {n}"),
         }
     }
@@ -51,31 +63,95 @@ impl From<Page> for u32 {
 pub enum Msg {
     Adding,
     Add(String),
+    Attach(Box<crate::tasks::Task>, Vec<std::path::PathBuf>),
     AskRefresh,
+    Bulk(Vec<usize>, crate::tasks::bulk::Action),
     Cancel,
+    /// Applies the daily capacity typed in the preferences.
+    Capacity,
+    CapacityChanged,
     Complete(Box<crate::tasks::Task>),
+    Delete(Vec<crate::tasks::Task>),
+    DismissUndo,
     Edit(Box<crate::tasks::Task>),
     EditCancel,
     EditDone(Box<crate::tasks::Task>),
+    ExportCalendar,
+    ExportCalendarTo(std::path::PathBuf),
+    ExportPage(crate::export::Format),
     Find,
     Help,
+    Import(Vec<crate::tasks::Task>),
+    Importing,
     Refresh,
+    Remind,
+    RemindComplete(String),
+    RemindEdit(String),
+    RenameTag(char, String, String),
+    Save(Box<crate::tasks::Task>),
     Search(String),
+    Snooze(String),
+    Timer(Box<crate::tasks::Task>),
+    Track(Box<crate::tasks::Task>, chrono::Duration),
+    Undo,
+}
+
+impl From<crate::widgets::task::MsgOutput> for Msg {
+    fn from(output: crate::widgets::task::MsgOutput) -> Self {
+        use crate::widgets::task::MsgOutput;
+
+        match output {
+            MsgOutput::Attach(task, files) => Msg::Attach(task, files),
+            MsgOutput::Bulk(ids, action) => Msg::Bulk(ids, action),
+            MsgOutput::Complete(task) => Msg::Complete(task),
+            MsgOutput::Delete(tasks) => Msg::Delete(tasks),
+            MsgOutput::Edit(task) => Msg::Edit(task),
+            MsgOutput::Save(task) => Msg::Save(task),
+            MsgOutput::Timer(task) => Msg::Timer(task),
+        }
+    }
+}
+
+impl From<crate::widgets::tags::MsgOutput> for Msg {
+    fn from(output: crate::widgets::tags::MsgOutput) -> Self {
+        use crate::widgets::tags::MsgOutput;
+
+        match output {
+            MsgOutput::Attach(task, files) => Msg::Attach(task, files),
+            MsgOutput::Bulk(ids, action) => Msg::Bulk(ids, action),
+            MsgOutput::Complete(task) => Msg::Complete(task),
+            MsgOutput::Delete(tasks) => Msg::Delete(tasks),
+            MsgOutput::Edit(task) => Msg::Edit(task),
+            MsgOutput::RenameTag(prefix, from, to) => Msg::RenameTag(prefix, from, to),
+            MsgOutput::Save(task) => Msg::Save(task),
+            MsgOutput::Timer(task) => Msg::Timer(task),
+        }
+    }
 }
 
 pub struct Model {
     agenda: relm4::Controller<crate::agenda::Model>,
+    capacity: chrono::Duration,
     config: todo_txt::Config,
     contexts: relm4::Controller<crate::widgets::tags::Model>,
+    dashboard: relm4::Controller<crate::dashboard::Model>,
+    /// Last deleted tasks, kept to undo the deletion.
+    deleted: Vec<crate::tasks::Task>,
     done: relm4::Controller<crate::done::Model>,
     edit: relm4::Controller<crate::edit::Model>,
     flag: relm4::Controller<crate::flag::Model>,
+    import: relm4::Controller<crate::import::Model>,
     inbox: relm4::Controller<crate::inbox::Model>,
     logger: relm4::Controller<crate::logger::Model>,
+    next: relm4::Controller<crate::next::Model>,
     projects: relm4::Controller<crate::widgets::tags::Model>,
+    reminder: crate::reminder::Scheduler,
+    review: relm4::Controller<crate::review::Model>,
     search: relm4::Controller<crate::search::Model>,
     shortcuts: gtk::ShortcutsWindow,
     tags: relm4::Controller<crate::widgets::tags::Model>,
+    timer: relm4::Controller<crate::timer::Model>,
+    today: relm4::Controller<crate::today::Model>,
     watcher: notify::RecommendedWatcher,
 }
 
@@ -107,13 +183,35 @@ impl Model {
         format!("/txt/todo/effitask/{stylesheet}")
     }
 
-    fn add_tab_widgets(&self, notebook: &gtk::Notebook) {
+    fn add_tab_widgets(&self, notebook: &gtk::Notebook, sender: &relm4::ComponentSender<Self>) {
         let n = notebook.n_pages();
 
         for x in 0..n {
             let page = notebook.nth_page(Some(x)).unwrap();
             let widget = self.tab_widget(x);
 
+            // Tasks dropped on the today, flag or done tab are planned, flagged
+            // or completed
+            let action: Option<fn() -> crate::tasks::bulk::Action> = match x.into() {
+                Page::Today => {
+                    Some(|| crate::tasks::bulk::Action::Plan(Some(crate::date::today())))
+                }
+                Page::Flag => Some(|| crate::tasks::bulk::Action::Flag(true)),
+                Page::Done => Some(|| crate::tasks::bulk::Action::Complete),
+                _ => None,
+            };
+
+            if let Some(action) = action {
+                let sender = sender.clone();
+
+                widget.add_controller(crate::widgets::task::task_drop_target(
+                    move |id, _, _, _| {
+                        sender.input(Msg::Bulk(vec![id], action()));
+                        true
+                    },
+                ));
+            }
+
             notebook.set_tab_label(&page, Some(&widget));
         }
     }
@@ -124,11 +222,15 @@ impl Model {
 
         let title = match n.into() {
             Page::Inbox => "inbox",
+            Page::Next => "next",
+            Page::Today => "today",
             Page::Projects => "projects",
             Page::Contexts => "contexts",
             Page::Agenda => "agenda",
             Page::Flag => "flag",
             Page::Done => "done",
+            Page::Dashboard => "dashboard",
+            Page::Review => "review",
             Page::Search => "search",
             Page::Tags => "tags",
         };
@@ -144,8 +246,14 @@ impl Model {
     }
 
     fn add(&mut self, widgets: &ModelWidgets, text: &str) {
+        let line = if widgets.natural_button.is_active() {
+            crate::tasks::natural::parse(text, crate::date::today()).line()
+        } else {
+            text.to_string()
+        };
+
         self.unwatch();
-        match globals::tasks::add(text) {
+        match globals::tasks::add(&line) {
             Ok(_) => self.update_tasks(widgets),
             Err(err) => log::error!("Unable to create task: '{err}'"),
         }
@@ -154,9 +262,245 @@ impl Model {
         widgets.add_popover.popdown();
     }
 
+    /// Shows the fields recognized in the new task while it's typed.
+    fn add_preview(entry: &gtk::Entry, preview: &gtk::Label, natural: &gtk::CheckButton) {
+        crate::widgets::completion::attach(entry, "+@#", || {
+            crate::tasks::completion::tags(&tasks())
+        });
+
+        let preview = preview.clone();
+        let natural = natural.clone();
+
+        entry.connect_changed(move |entry| {
+            let text = entry.text();
+
+            if text.trim().is_empty() {
+                preview.set_text(ADD_HINT);
+                return;
+            }
+
+            if !natural.is_active() {
+                preview.set_markup(&format!("<tt>{}</tt>", crate::tasks::markup::escape(&text)));
+                return;
+            }
+
+            let parsed = crate::tasks::natural::parse(&text, crate::date::today());
+            let line = crate::tasks::markup::escape(&parsed.line());
+
+            match parsed.summary().as_str() {
+                "" => preview.set_markup(&format!("<tt>{line}</tt>")),
+                summary => preview.set_markup(&format!(
+                    "{}\n<tt>{line}</tt>",
+                    crate::tasks::markup::escape(summary)
+                )),
+            }
+        });
+    }
+
+    fn attach(
+        &mut self,
+        widgets: &ModelWidgets,
+        task: &crate::tasks::Task,
+        files: &[std::path::PathBuf],
+    ) {
+        let mut list = tasks();
+
+        let Some(t) = list.tasks.get_mut(task.id) else {
+            return;
+        };
+
+        if let Err(err) = crate::tasks::attachment::attach(t, files) {
+            log::error!("{err}");
+            return;
+        }
+
+        t.touch(crate::date::today());
+
+        match self.write_tasks(&list) {
+            Ok(_) => log::info!("{} files attached", files.len()),
+            Err(err) => log::error!("Unable to save tasks: {err}"),
+        }
+
+        self.update_tasks(widgets);
+    }
+
+    fn bulk(&mut self, widgets: &ModelWidgets, ids: &[usize], action: &crate::tasks::bulk::Action) {
+        let mut list = tasks();
+
+        if let crate::tasks::bulk::Action::Delete { notes } = action {
+            self.delete(widgets, &mut list, ids, *notes);
+            return;
+        }
+
+        let old = list
+            .tasks
+            .iter()
+            .filter(|x| ids.contains(&x.id))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        crate::tasks::bulk::apply(&mut list.tasks, ids, action);
+
+        // Reordering the plan isn't a change of the tasks themselves
+        if !matches!(action, crate::tasks::bulk::Action::Plan(_)) {
+            for task in list.tasks.iter_mut().filter(|x| ids.contains(&x.id)) {
+                task.touch(crate::date::today());
+            }
+        }
+
+        match self.write_tasks(&list) {
+            Ok(_) => {
+                for old in old {
+                    self.follow(&old, &list.tasks[old.id]);
+                }
+
+                log::info!("{} tasks updated", ids.len());
+            }
+            Err(err) => log::error!("Unable to save tasks: {err}"),
+        }
+
+        self.update_tasks(widgets);
+    }
+
+    fn confirm_delete(
+        root: &gtk::ApplicationWindow,
+        sender: &relm4::ComponentSender<Self>,
+        tasks: Vec<crate::tasks::Task>,
+    ) {
+        let heading = match tasks.as_slice() {
+            [task] => format!("Delete “{}”?", task.subject),
+            _ => format!("Delete {} tasks?", tasks.len()),
+        };
+
+        let dialog = adw::AlertDialog::new(
+            Some(&heading),
+            Some("Tasks can be restored until the next deletion."),
+        );
+        dialog.add_responses(&[("cancel", "Cancel"), ("delete", "Delete")]);
+        dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+        dialog.set_close_response("cancel");
+
+        let has_notes = tasks
+            .iter()
+            .any(|x| matches!(x.note, todo_txt::task::Note::Long { .. }));
+
+        if has_notes {
+            dialog.add_response("notes", "Delete with notes");
+            dialog.set_response_appearance("notes", adw::ResponseAppearance::Destructive);
+        }
+
+        let ids = tasks.iter().map(|x| x.id).collect::<Vec<_>>();
+        let sender = sender.clone();
+
+        dialog.connect_response(None, move |_, response| {
+            let notes = match response {
+                "delete" => false,
+                "notes" => true,
+                _ => return,
+            };
+
+            sender.input(Msg::Bulk(
+                ids.clone(),
+                crate::tasks::bulk::Action::Delete { notes },
+            ));
+        });
+
+        dialog.present(Some(root));
+    }
+
+    fn delete(
+        &mut self,
+        widgets: &ModelWidgets,
+        list: &mut crate::tasks::List,
+        ids: &[usize],
+        notes: bool,
+    ) {
+        let deleted = list
+            .tasks
+            .iter()
+            .filter(|x| ids.contains(&x.id))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        crate::tasks::bulk::apply(
+            &mut list.tasks,
+            ids,
+            &crate::tasks::bulk::Action::Delete { notes },
+        );
+
+        if let Err(err) = self.write_tasks(list) {
+            log::error!("Unable to delete tasks: {err}");
+            return;
+        }
+
+        self.deleted = deleted;
+
+        if notes {
+            let notes_dir = crate::tasks::attachment::notes_dir();
+
+            for task in &self.deleted {
+                if let todo_txt::task::Note::Long { filename, .. } = &task.note
+                    && let Err(err) = std::fs::remove_file(notes_dir.join(filename))
+                {
+                    log::warn!("Unable to remove note {filename}: {err}");
+                }
+            }
+        }
+
+        log::info!("{} tasks deleted", self.deleted.len());
+
+        widgets
+            .undo_label
+            .set_text(&format!("{} tasks deleted", self.deleted.len()));
+        widgets.undo.set_visible(true);
+        self.edit.widget().set_visible(false);
+        self.update_tasks(widgets);
+    }
+
+    fn undo(&mut self, widgets: &ModelWidgets) {
+        let mut list = tasks();
+        let count = self.deleted.len();
+
+        // Task ids are their position in the list before the deletion
+        for task in self.deleted.drain(..) {
+            list.insert(task.id, task);
+        }
+
+        match self.write_tasks(&list) {
+            Ok(_) => log::info!("{count} tasks restored"),
+            Err(err) => log::error!("Unable to restore tasks: {err}"),
+        }
+
+        widgets.undo.set_visible(false);
+        self.update_tasks(widgets);
+    }
+
+    fn import(&mut self, widgets: &ModelWidgets, imported: Vec<crate::tasks::Task>) {
+        let mut list = tasks();
+        let count = imported.len();
+
+        for mut task in imported {
+            if task.create_date.is_none() {
+                task.create_date = Some(crate::date::today());
+            }
+
+            list.append(task);
+        }
+
+        match self.write_tasks(&list) {
+            Ok(_) => log::info!("{count} tasks imported"),
+            Err(err) => log::error!("Unable to import tasks: {err}"),
+        }
+
+        self.update_tasks(widgets);
+    }
+
     fn complete(&mut self, widgets: &ModelWidgets, task: &crate::tasks::Task) {
         let id = task.id;
         let mut list = tasks();
+        let Some(old) = list.tasks.get(id).cloned() else {
+            return;
+        };
 
         if let Some(ref mut t) = list.tasks.get_mut(id) {
             if t.finished {
@@ -164,6 +508,8 @@ impl Model {
             } else {
                 t.complete();
             }
+
+            t.touch(crate::date::today());
         } else {
             return;
         }
@@ -171,28 +517,15 @@ impl Model {
         let t = list.tasks[id].clone();
 
         if t.finished {
-            if let Some(ref recurrence) = t.recurrence {
-                let due = if recurrence.strict && t.due_date.is_some() {
-                    t.due_date.unwrap()
-                } else {
-                    crate::date::today()
-                };
-
-                let mut new: crate::tasks::Task = t.clone();
-                new.uncomplete();
-                new.create_date = Some(crate::date::today());
-                new.due_date = Some(recurrence.clone() + due);
-
-                if let Some(threshold_date) = t.threshold_date {
-                    new.threshold_date = Some(recurrence.clone() + threshold_date);
-                }
-
+            if let Some(new) = t.next_occurrence() {
                 list.push(new);
             }
         }
 
         match self.write_tasks(&list) {
             Ok(_) => {
+                self.follow(&old, &list.tasks[id]);
+
                 if list.tasks[id].finished {
                     log::info!("Task done");
                 } else {
@@ -205,6 +538,15 @@ impl Model {
         self.update_tasks(widgets);
     }
 
+    /// Keeps the timer and the pending reminders on `old` after it changed.
+    fn follow(&mut self, old: &crate::tasks::Task, new: &crate::tasks::Task) {
+        self.reminder.follow(old, new);
+        self.timer.emit(crate::timer::Msg::Follow(
+            Box::new(old.clone()),
+            Box::new(new.clone()),
+        ));
+    }
+
     fn edit(&mut self, task: &crate::tasks::Task) {
         self.edit
             .emit(crate::edit::MsgInput::Set(Box::new(task.clone())));
@@ -215,8 +557,11 @@ impl Model {
         let id = task.id;
         let mut list = tasks();
 
-        if list.tasks.get_mut(id).is_some() {
-            list.tasks[id] = task.clone();
+        if let Some(old) = list.tasks.get(id).cloned() {
+            let mut task = task.clone();
+            task.touch(crate::date::today());
+            self.follow(&old, &task);
+            list.tasks[id] = task;
         }
 
         match self.write_tasks(&list) {
@@ -227,7 +572,227 @@ impl Model {
         log::info!("Task updated");
 
         self.update_tasks(widgets);
-        self.edit.widget().set_visible(false);
+    }
+
+    /// Adds the time spent to the task and to the time log.
+    fn track(
+        &mut self,
+        widgets: &ModelWidgets,
+        root: &gtk::ApplicationWindow,
+        task: &crate::tasks::Task,
+        duration: chrono::Duration,
+    ) {
+        let line = task.to_string();
+        let entry = crate::tasks::tracking::Entry {
+            date: crate::date::today(),
+            duration,
+            task: line.clone(),
+        };
+
+        match crate::tasks::tracking::append(&entry) {
+            Ok(_) => log::info!(
+                "{} tracked on “{}”",
+                crate::date::format_duration(duration),
+                task.subject
+            ),
+            Err(err) => log::error!("{err}"),
+        }
+
+        let mut list = tasks();
+        let position = if list
+            .tasks
+            .get(task.id)
+            .is_some_and(|x| x.to_string() == line)
+        {
+            Some(task.id)
+        } else {
+            list.tasks.iter().position(|x| x.to_string() == line)
+        };
+
+        let Some(position) = position else {
+            let dialog = adw::AlertDialog::new(
+                Some("Time not added to the task"),
+                Some(&format!(
+                    "“{}” changed or was deleted since the timer started, the {} spent are only in the time log.",
+                    task.subject,
+                    crate::date::format_duration(duration)
+                )),
+            );
+            dialog.add_response("close", "Close");
+            dialog.present(Some(root));
+            return;
+        };
+
+        let old = list.tasks[position].clone();
+        list.tasks[position].add_spent(duration);
+        list.tasks[position].touch(crate::date::today());
+
+        if let Err(err) = self.write_tasks(&list) {
+            log::error!("Unable to save tasks: {err}");
+            return;
+        }
+
+        self.follow(&old, &list.tasks[position]);
+        self.update_tasks(widgets);
+    }
+
+    fn rename_tag(&mut self, widgets: &ModelWidgets, prefix: char, from: &str, to: &str) {
+        let mut list = tasks();
+        let old = list.tasks.clone();
+        let changed = crate::tasks::rename::rename(&mut list.tasks, prefix, from, to);
+
+        if prefix == '+' {
+            let mut projects = crate::tasks::project::load();
+            let previous = projects.clone();
+            crate::tasks::project::rename(&mut projects, from, to);
+
+            if projects != previous
+                && let Err(err) = crate::tasks::project::save(&projects)
+            {
+                log::error!("{err}");
+            }
+        }
+
+        match self.write_tasks(&list) {
+            Ok(_) => {
+                for (old, new) in old.iter().zip(&list.tasks).filter(|(old, new)| old != new) {
+                    self.follow(old, new);
+                }
+
+                log::info!("{prefix}{from} renamed to {prefix}{to} in {changed} tasks");
+            }
+            Err(err) => log::error!("Unable to save tasks: {err}"),
+        }
+
+        self.update_tasks(widgets);
+    }
+
+    fn export_calendar(&mut self, widgets: &ModelWidgets, path: &std::path::Path) {
+        let mut list = tasks();
+
+        if crate::export::ics::assign_uids(&mut list.tasks) {
+            match self.write_tasks(&list) {
+                Ok(()) => self.update_tasks(widgets),
+                Err(err) => log::error!("{err}"),
+            }
+        }
+
+        let calendar = crate::export::ics::calendar(&crate::export::ics::tasks(&list.tasks));
+
+        match crate::export::write(path, &calendar) {
+            Ok(()) => log::info!("Calendar exported to {}", path.display()),
+            Err(err) => log::error!("{err}"),
+        }
+    }
+
+    fn export_page(
+        &self,
+        widgets: &ModelWidgets,
+        root: &gtk::ApplicationWindow,
+        format: crate::export::Format,
+    ) {
+        let report = self.report(widgets);
+        let content = format.render(&report);
+        let name = crate::export::file_name(&report.title, format);
+
+        Self::save_dialog(root, &name, move |path| {
+            match crate::export::write(&path, &content) {
+                Ok(()) => log::info!("Page exported to {}", path.display()),
+                Err(err) => log::error!("{err}"),
+            }
+        });
+    }
+
+    fn report(&self, widgets: &ModelWidgets) -> crate::export::Report {
+        use crate::export::Report;
+
+        let page = widgets.notebook.current_page().unwrap_or_default();
+
+        match page.into() {
+            Page::Inbox => Report::new("Inbox", crate::inbox::Model::tasks()),
+            Page::Next => Report::new("Next actions", self.next.model().tasks()),
+            Page::Today => Report::new("Today", crate::today::Model::tasks()),
+            Page::Projects => Self::tags_report(&self.projects),
+            Page::Contexts => Self::tags_report(&self.contexts),
+            Page::Tags => Self::tags_report(&self.tags),
+            Page::Agenda => {
+                let agenda = self.agenda.model();
+
+                Report {
+                    title: agenda.title(),
+                    sections: agenda.sections(),
+                }
+            }
+            Page::Flag => Report::new("Flagged", crate::flag::Model::tasks()),
+            Page::Done => Report::new("Done", crate::done::Model::tasks()),
+            Page::Dashboard => Report {
+                title: "Dashboard".to_string(),
+                sections: crate::dashboard::Model::sections(),
+            },
+            Page::Review => {
+                let review = self.review.model();
+
+                Report::new(&review.title(), review.tasks())
+            }
+            Page::Search => Report::new(
+                &format!("Search {}", widgets.search.text()),
+                crate::search::Model::tasks(),
+            ),
+        }
+    }
+
+    fn tags_report(tags: &relm4::Controller<crate::widgets::tags::Model>) -> crate::export::Report {
+        let tags = tags.model();
+
+        crate::export::Report::new(&tags.title(), tags.tasks())
+    }
+
+    fn save_dialog<F: FnOnce(std::path::PathBuf) + 'static>(
+        root: &gtk::ApplicationWindow,
+        initial_name: &str,
+        f: F,
+    ) {
+        let dialog = gtk::FileDialog::builder()
+            .initial_name(initial_name)
+            .modal(true)
+            .build();
+
+        dialog.save(Some(root), None::<&gtk::gio::Cancellable>, move |result| {
+            if let Some(path) = result.ok().and_then(|x| x.path()) {
+                f(path);
+            }
+        });
+    }
+
+    /// Marks the capacity entry when its text isn't a duration.
+    fn check_capacity(widgets: &ModelWidgets) -> Option<chrono::Duration> {
+        let text = widgets.capacity_entry.text();
+        let capacity = if text.trim().is_empty() {
+            Some(DEFAULT_CAPACITY)
+        } else {
+            crate::date::parse_duration(text.trim())
+        };
+
+        if capacity.is_some() {
+            widgets.capacity_entry.remove_css_class("error");
+            widgets.capacity_entry.set_tooltip_text(Some(CAPACITY_HINT));
+        } else {
+            widgets.capacity_entry.add_css_class("error");
+            widgets
+                .capacity_entry
+                .set_tooltip_text(Some("Invalid duration, like 8h or 7h30m"));
+        }
+
+        capacity
+    }
+
+    fn apply_capacity(&mut self, widgets: &ModelWidgets) {
+        if let Some(capacity) = Self::check_capacity(widgets)
+            && capacity != self.capacity
+        {
+            self.capacity = capacity;
+            self.update_tasks(widgets);
+        }
     }
 
     fn search(&self, widgets: &ModelWidgets, query: &str) {
@@ -248,6 +813,8 @@ impl Model {
         globals::tasks::replace(list);
 
         globals::preferences::replace(crate::application::Preferences {
+            capacity: Some(self.capacity),
+            completed: widgets.completed_button.is_active(),
             defered: widgets.defered_button.is_active(),
             done: widgets.done_button.is_active(),
             hidden: widgets.hidden_button.is_active(),
@@ -257,16 +824,20 @@ impl Model {
         self.contexts
             .sender()
             .emit(crate::widgets::tags::MsgInput::Update);
+        self.dashboard.emit(crate::dashboard::Msg::Update);
         self.done.sender().emit(crate::done::Msg::Update);
         self.projects
             .sender()
             .emit(crate::widgets::tags::MsgInput::Update);
         self.flag.sender().emit(crate::flag::Msg::Update);
         self.inbox.sender().emit(crate::inbox::Msg::Update);
+        self.next.emit(crate::next::Msg::Update);
+        self.review.emit(crate::review::MsgInput::Update);
         self.search.sender().emit(crate::search::MsgInput::Update);
         self.tags
             .sender()
             .emit(crate::widgets::tags::MsgInput::Update);
+        self.today.sender().emit(crate::today::Msg::Update);
     }
 
     fn watch(&mut self) {
@@ -322,6 +893,43 @@ impl Model {
         window.add_controller(controller);
     }
 
+    fn notification_actions(sender: relm4::ComponentSender<Self>) {
+        let app = relm4::main_application();
+
+        Self::add_action(
+            &app,
+            "complete",
+            gtk::glib::clone!(
+                #[strong]
+                sender,
+                move |key| sender.input(Msg::RemindComplete(key))
+            ),
+        );
+
+        Self::add_action(
+            &app,
+            "edit",
+            gtk::glib::clone!(
+                #[strong]
+                sender,
+                move |key| sender.input(Msg::RemindEdit(key))
+            ),
+        );
+
+        Self::add_action(&app, "snooze", move |key| sender.input(Msg::Snooze(key)));
+    }
+
+    fn add_action<F: Fn(String) + 'static>(app: &gtk::Application, name: &str, f: F) {
+        let action = gtk::gio::SimpleAction::new(name, Some(gtk::glib::VariantTy::STRING));
+        action.connect_activate(move |_, parameter| {
+            if let Some(key) = parameter.and_then(|x| x.get::<String>()) {
+                f(key);
+            }
+        });
+
+        app.add_action(&action);
+    }
+
     fn write_tasks(&mut self, list: &crate::tasks::List) -> Result<(), String> {
         self.unwatch();
         let result = list.write();
@@ -355,70 +963,76 @@ impl relm4::Component for Model {
 
         let agenda = crate::agenda::Model::builder()
             .launch(crate::date::today())
-            .forward(sender.input_sender(), |output| match output {
-                crate::widgets::task::MsgOutput::Complete(task) => Msg::Complete(task),
-                crate::widgets::task::MsgOutput::Edit(task) => Msg::Edit(task),
-            });
+            .forward(sender.input_sender(), Msg::from);
 
         let contexts = crate::widgets::tags::Model::builder()
             .launch(crate::widgets::tags::Type::Contexts)
-            .forward(sender.input_sender(), |output| match output {
-                crate::widgets::tags::MsgOutput::Complete(task) => Msg::Complete(task),
-                crate::widgets::tags::MsgOutput::Edit(task) => Msg::Edit(task),
-            });
+            .forward(sender.input_sender(), Msg::from);
 
-        let done =
-            crate::done::Model::builder()
-                .launch(())
-                .forward(sender.input_sender(), |output| match output {
-                    crate::widgets::task::MsgOutput::Complete(task) => Msg::Complete(task),
-                    crate::widgets::task::MsgOutput::Edit(task) => Msg::Edit(task),
-                });
+        let done = crate::done::Model::builder()
+            .launch(())
+            .forward(sender.input_sender(), Msg::from);
 
         let edit = crate::edit::Model::builder()
             .launch(crate::tasks::Task::new())
             .forward(sender.input_sender(), |output| match output {
                 crate::edit::MsgOutput::Cancel => Msg::EditCancel,
+                crate::edit::MsgOutput::Delete(task) => Msg::Delete(vec![*task]),
                 crate::edit::MsgOutput::Done(task) => Msg::EditDone(task),
             });
 
-        let flag =
-            crate::flag::Model::builder()
-                .launch(())
-                .forward(sender.input_sender(), |output| match output {
-                    crate::widgets::task::MsgOutput::Complete(task) => Msg::Complete(task),
-                    crate::widgets::task::MsgOutput::Edit(task) => Msg::Edit(task),
-                });
+        let dashboard = crate::dashboard::Model::builder().launch(()).detach();
+
+        let flag = crate::flag::Model::builder()
+            .launch(())
+            .forward(sender.input_sender(), Msg::from);
 
-        let inbox =
-            crate::inbox::Model::builder()
+        let import =
+            crate::import::Model::builder()
                 .launch(())
                 .forward(sender.input_sender(), |output| match output {
-                    crate::widgets::task::MsgOutput::Complete(task) => Msg::Complete(task),
-                    crate::widgets::task::MsgOutput::Edit(task) => Msg::Edit(task),
+                    crate::import::MsgOutput::Import(tasks) => Msg::Import(tasks),
                 });
 
+        let inbox = crate::inbox::Model::builder()
+            .launch(())
+            .forward(sender.input_sender(), Msg::from);
+
+        let next = crate::next::Model::builder()
+            .launch(())
+            .forward(sender.input_sender(), Msg::from);
+
         let projects = crate::widgets::tags::Model::builder()
             .launch(crate::widgets::tags::Type::Projects)
-            .forward(sender.input_sender(), |output| match output {
-                crate::widgets::tags::MsgOutput::Complete(task) => Msg::Complete(task),
-                crate::widgets::tags::MsgOutput::Edit(task) => Msg::Edit(task),
-            });
+            .forward(sender.input_sender(), Msg::from);
 
-        let search =
-            crate::search::Model::builder()
+        let review =
+            crate::review::Model::builder()
                 .launch(())
                 .forward(sender.input_sender(), |output| match output {
-                    crate::widgets::task::MsgOutput::Complete(task) => Msg::Complete(task),
-                    crate::widgets::task::MsgOutput::Edit(task) => Msg::Edit(task),
+                    crate::review::MsgOutput::Add(task) => Msg::Add(task),
+                    crate::review::MsgOutput::Task(output) => Msg::from(output),
+                    crate::review::MsgOutput::Updated => Msg::Refresh,
                 });
 
+        let search = crate::search::Model::builder()
+            .launch(())
+            .forward(sender.input_sender(), Msg::from);
+
         let tags = crate::widgets::tags::Model::builder()
             .launch(crate::widgets::tags::Type::Hashtags)
-            .forward(sender.input_sender(), |output| match output {
-                crate::widgets::tags::MsgOutput::Complete(task) => Msg::Complete(task),
-                crate::widgets::tags::MsgOutput::Edit(task) => Msg::Edit(task),
-            });
+            .forward(sender.input_sender(), Msg::from);
+
+        let timer =
+            crate::timer::Model::builder()
+                .launch(())
+                .forward(sender.input_sender(), |output| match output {
+                    crate::timer::MsgOutput::Track(task, duration) => Msg::Track(task, duration),
+                });
+
+        let today = crate::today::Model::builder()
+            .launch(())
+            .forward(sender.input_sender(), Msg::from);
 
         let builder = gtk::Builder::from_resource("/txt/todo/effitask/shortcuts.ui");
         let shortcuts = builder.object("shortcuts").unwrap();
@@ -440,17 +1054,26 @@ impl relm4::Component for Model {
         let mut model = Self {
             watcher,
             agenda,
+            capacity: DEFAULT_CAPACITY,
             config: init,
             contexts,
+            dashboard,
+            deleted: Vec::new(),
             done,
             edit,
             flag,
+            import,
             inbox,
             logger,
+            next,
             projects,
+            reminder: crate::reminder::Scheduler::new(),
+            review,
             search,
             shortcuts,
             tags,
+            timer,
+            today,
         };
 
         model.watch();
@@ -458,15 +1081,37 @@ impl relm4::Component for Model {
         let widgets = view_output!();
 
         model.load_style();
-        model.add_tab_widgets(&widgets.notebook);
+        model.add_tab_widgets(&widgets.notebook, &sender);
         model.update_tasks(&widgets);
         model.search.widget().set_visible(false);
 
+        Self::add_preview(
+            &widgets.add_entry,
+            &widgets.add_preview,
+            &widgets.natural_button,
+        );
+
         Self::check_button_set_markup(&widgets.defered_button);
         Self::check_button_set_markup(&widgets.done_button);
         Self::check_button_set_markup(&widgets.hidden_button);
-
-        Self::shortcuts(&root, sender);
+        Self::check_button_set_markup(&widgets.completed_button);
+
+        let focus = gtk::EventControllerFocus::new();
+        focus.connect_leave(gtk::glib::clone!(
+            #[strong]
+            sender,
+            move |_| sender.input(Msg::Capacity)
+        ));
+        widgets.capacity_entry.add_controller(focus);
+
+        Self::shortcuts(&root, sender.clone());
+        Self::notification_actions(sender.clone());
+
+        sender.input(Msg::Remind);
+        gtk::glib::timeout_add_seconds_local(30, move || {
+            sender.input(Msg::Remind);
+            gtk::glib::ControlFlow::Continue
+        });
 
         relm4::ComponentParts { model, widgets }
     }
@@ -475,28 +1120,76 @@ impl relm4::Component for Model {
         &mut self,
         widgets: &mut Self::Widgets,
         msg: Self::Input,
-        _: relm4::ComponentSender<Self>,
-        _: &Self::Root,
+        sender: relm4::ComponentSender<Self>,
+        root: &Self::Root,
     ) {
         match msg {
             Msg::Add(task) => self.add(widgets, &task),
             Msg::Adding => widgets.add_popover.popup(),
+            Msg::Attach(task, files) => self.attach(widgets, &task, &files),
+            Msg::Bulk(ids, action) => self.bulk(widgets, &ids, &action),
             Msg::AskRefresh => widgets.ask.set_visible(true),
             Msg::Cancel => widgets.ask.set_visible(false),
+            Msg::Capacity => self.apply_capacity(widgets),
+            Msg::CapacityChanged => {
+                Self::check_capacity(widgets);
+            }
             Msg::Complete(task) => self.complete(widgets, &task),
+            Msg::Delete(tasks) => Self::confirm_delete(root, &sender, tasks),
+            Msg::DismissUndo => widgets.undo.set_visible(false),
             Msg::EditCancel => self.edit.widget().set_visible(false),
-            Msg::EditDone(task) => self.save(widgets, &task),
+            Msg::EditDone(task) => {
+                self.save(widgets, &task);
+                self.edit.widget().set_visible(false);
+            }
             Msg::Edit(task) => self.edit(&task),
+            Msg::ExportCalendar => {
+                widgets.export_popover.popdown();
+                Self::save_dialog(root, "effitask.ics", move |path| {
+                    sender.input(Msg::ExportCalendarTo(path));
+                });
+            }
+            Msg::ExportCalendarTo(path) => self.export_calendar(widgets, &path),
+            Msg::ExportPage(format) => {
+                widgets.export_popover.popdown();
+                self.export_page(widgets, root, format);
+            }
             Msg::Find => {
                 widgets.search.grab_focus();
             }
             Msg::Help => self.shortcuts.present(),
+            Msg::Import(tasks) => self.import(widgets, tasks),
+            Msg::Importing => {
+                self.import.widget().set_transient_for(Some(root));
+                self.import.widget().present();
+            }
             Msg::Refresh => {
                 self.update_tasks(widgets);
                 widgets.ask.set_visible(false);
                 log::info!("Tasks reloaded");
             }
+            Msg::Remind => self.reminder.check(&tasks()),
+            Msg::RemindComplete(key) => {
+                relm4::main_application().withdraw_notification(&key);
+
+                match self.reminder.find(&key) {
+                    Some(task) if !task.finished => self.complete(widgets, &task),
+                    _ => (),
+                }
+            }
+            Msg::RemindEdit(key) => {
+                if let Some(task) = self.reminder.find(&key) {
+                    self.edit(&task);
+                    root.present();
+                }
+            }
+            Msg::RenameTag(prefix, from, to) => self.rename_tag(widgets, prefix, &from, &to),
+            Msg::Save(task) => self.save(widgets, &task),
             Msg::Search(query) => self.search(widgets, &query),
+            Msg::Snooze(key) => self.reminder.snooze(key),
+            Msg::Timer(task) => self.timer.emit(crate::timer::Msg::Start(task)),
+            Msg::Track(task, duration) => self.track(widgets, root, &task, duration),
+            Msg::Undo => self.undo(widgets),
         }
     }
 
@@ -515,6 +1208,7 @@ impl relm4::Component for Model {
 
                         connect_clicked => Msg::Refresh,
                     },
+                    pack_end: model.timer.widget(),
                     pack_start = &gtk::MenuButton {
                         set_icon_name: "list-add",
                         set_tooltip_text: "Add".into(),
@@ -524,6 +1218,7 @@ impl relm4::Component for Model {
                             gtk::Box {
                                 set_orientation: gtk::Orientation::Vertical,
 
+                                #[name = "add_entry"]
                                 gtk::Entry {
                                     set_input_purpose: gtk::InputPurpose::FreeForm,
                                     set_input_hints: gtk::InputHints::SPELLCHECK,
@@ -533,8 +1228,15 @@ impl relm4::Component for Model {
                                         this.set_text("");
                                     },
                                 },
+                                #[name = "add_preview"]
                                 gtk::Label {
-                                    set_text: "Create a new task +project @context due:2042-01-01",
+                                    set_text: ADD_HINT,
+                                },
+                                #[name = "natural_button"]
+                                gtk::CheckButton {
+                                    set_active: true,
+                                    set_label: Some("Recognize dates, times and priorities"),
+                                    set_tooltip_text: Some("Uncheck to add the text as a todo.txt line"),
                                 },
                             },
                         },
@@ -573,6 +1275,74 @@ impl relm4::Component for Model {
 
                                     connect_toggled => Msg::Refresh,
                                 },
+                                #[name = "completed_button"]
+                                gtk::CheckButton {
+                                    #[wrap(Some)]
+                                    set_child = &gtk::Label::new(
+                                        Some("Display <b>completed</b> projects"),
+                                    ),
+
+                                    connect_toggled => Msg::Refresh,
+                                },
+                                #[name = "capacity_entry"]
+                                gtk::Entry {
+                                    set_placeholder_text: Some("Daily capacity (8h)"),
+                                    set_text: "8h",
+                                    set_tooltip_text: Some(CAPACITY_HINT),
+
+                                    connect_activate => Msg::Capacity,
+                                    connect_changed => Msg::CapacityChanged,
+                                },
+                            },
+                        },
+                    },
+                    pack_start = &gtk::Button {
+                        set_icon_name: "document-open",
+                        set_tooltip_text: "Import".into(),
+
+                        connect_clicked => Msg::Importing,
+                    },
+                    pack_start = &gtk::MenuButton {
+                        set_icon_name: "document-save-as",
+                        set_tooltip_text: "Export".into(),
+                        #[wrap(Some)]
+                        #[name = "export_popover"]
+                        set_popover = &gtk::Popover {
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+
+                                gtk::Button {
+                                    set_label: "Current page as Markdown",
+                                    add_css_class: "flat",
+
+                                    connect_clicked => Msg::ExportPage(crate::export::Format::Markdown),
+                                },
+                                gtk::Button {
+                                    set_label: "Current page as CSV",
+                                    add_css_class: "flat",
+
+                                    connect_clicked => Msg::ExportPage(crate::export::Format::Csv),
+                                },
+                                gtk::Button {
+                                    set_label: "Current page as JSON",
+                                    add_css_class: "flat",
+
+                                    connect_clicked => Msg::ExportPage(crate::export::Format::Json),
+                                },
+                                gtk::Button {
+                                    set_label: "Current page as HTML report",
+                                    add_css_class: "flat",
+
+                                    connect_clicked => Msg::ExportPage(crate::export::Format::Html),
+                                },
+                                gtk::Separator {
+                                },
+                                gtk::Button {
+                                    set_label: "Due tasks as calendar (ICS)",
+                                    add_css_class: "flat",
+
+                                    connect_clicked => Msg::ExportCalendar,
+                                },
                             },
                         },
                     },
@@ -611,6 +1381,26 @@ impl relm4::Component for Model {
                         connect_clicked => Msg::Cancel,
                     },
                 },
+                #[name = "undo"]
+                gtk::Box {
+                    add_css_class: "ask",
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_visible: false,
+
+                    #[name = "undo_label"]
+                    gtk::Label {
+                        set_hexpand: true,
+                    },
+                    gtk::Button {
+                        add_css_class: "suggested-action",
+                        set_label: "Undo",
+                        connect_clicked => Msg::Undo,
+                    },
+                    gtk::Button {
+                        set_label: "Dismiss",
+                        connect_clicked => Msg::DismissUndo,
+                    },
+                },
                 gtk::Paned {
                     set_hexpand: true,
                     set_vexpand: true,
@@ -623,12 +1413,16 @@ impl relm4::Component for Model {
                         set_tab_pos: gtk::PositionType::Left,
 
                         append_page: (model.inbox.widget(), None::<&gtk::Label>),
+                        append_page: (model.next.widget(), None::<&gtk::Label>),
+                        append_page: (model.today.widget(), None::<&gtk::Label>),
                         append_page: (model.projects.widget(), None::<&gtk::Label>),
                         append_page: (model.contexts.widget(), None::<&gtk::Label>),
                         append_page: (model.tags.widget(), None::<&gtk::Label>),
                         append_page: (model.agenda.widget(), None::<&gtk::Label>),
                         append_page: (model.flag.widget(), None::<&gtk::Label>),
                         append_page: (model.done.widget(), None::<&gtk::Label>),
+                        append_page: (model.dashboard.widget(), None::<&gtk::Label>),
+                        append_page: (model.review.widget(), None::<&gtk::Label>),
                         append_page: (model.search.widget(), None::<&gtk::Label>),
                     },
                     #[wrap(Some)]
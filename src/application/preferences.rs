@@ -1,5 +1,9 @@
 #[derive(Clone, Default)]
 pub struct Preferences {
+    /// Estimated work per day, the agenda warns above it.
+    pub capacity: Option<chrono::Duration>,
+    /// Also lists finished projects.
+    pub completed: bool,
     pub defered: bool,
     pub done: bool,
     pub hidden: bool,
@@ -0,0 +1,343 @@
+use adw::prelude::*;
+
+#[derive(Debug)]
+pub enum Msg {
+    Update,
+}
+
+pub struct Model {
+    dashboard: std::rc::Rc<std::cell::RefCell<crate::tasks::dashboard::Dashboard>>,
+}
+
+impl Model {
+    /// Overdue tasks and the ones completed this week, for exports.
+    pub fn sections() -> Vec<(String, Vec<crate::tasks::Task>)> {
+        use chrono::Datelike as _;
+
+        let today = crate::date::today();
+        let monday = today - chrono::Duration::days(today.weekday().num_days_from_monday().into());
+        let list = crate::application::tasks();
+
+        let overdue = list
+            .tasks
+            .iter()
+            .filter(|x| !x.finished && x.due_date.is_some_and(|due| due < today))
+            .cloned()
+            .collect();
+        let completed = list
+            .tasks
+            .iter()
+            .filter(|x| x.finish_date.is_some_and(|finished| finished >= monday))
+            .cloned()
+            .collect();
+
+        vec![
+            ("Overdue".to_string(), overdue),
+            ("Completed this week".to_string(), completed),
+        ]
+    }
+
+    fn update_dashboard(&self, widgets: &ModelWidgets) {
+        let list = crate::application::tasks();
+        let dashboard = crate::tasks::dashboard::Dashboard::new(&list.tasks, crate::date::today());
+
+        let streaks = dashboard
+            .streaks
+            .iter()
+            .map(|x| format!("{}: {} in a row (best {})", x.subject, x.current, x.best))
+            .collect::<Vec<_>>();
+
+        if streaks.is_empty() {
+            widgets.streaks.set_text("No recurring task completed yet");
+        } else {
+            widgets.streaks.set_text(&streaks.join("\n"));
+        }
+
+        *self.dashboard.borrow_mut() = dashboard;
+
+        self.update_tracking(widgets);
+
+        for chart in [
+            &widgets.days,
+            &widgets.weeks,
+            &widgets.overdue,
+            &widgets.priorities,
+            &widgets.contexts,
+            &widgets.projects,
+        ] {
+            chart.queue_draw();
+        }
+    }
+
+    /// Time spent between the range entries, the last week by default.
+    fn update_tracking(&self, widgets: &ModelWidgets) {
+        let today = crate::date::today();
+        let date = |entry: &gtk::Entry, default| {
+            let text = entry.text();
+
+            if text.trim().is_empty() {
+                return default;
+            }
+
+            chrono::NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").unwrap_or_else(|err| {
+                log::warn!("Invalid date {text:?}: {err}");
+                default
+            })
+        };
+
+        let from = date(&widgets.from, today - chrono::Duration::days(6));
+        let to = date(&widgets.to, today);
+        let report = crate::tasks::tracking::Report::new(&crate::tasks::tracking::load(), from, to);
+
+        let lines = |prefix: char, sums: &[(String, chrono::Duration)]| {
+            sums.iter()
+                .map(|(name, duration)| {
+                    format!(
+                        "{prefix}{name}: {}",
+                        crate::date::format_duration(*duration)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        widgets.tracking_total.set_text(&format!(
+            "{} tracked from {} to {}",
+            crate::date::format_duration(report.total),
+            from.format("%Y-%m-%d"),
+            to.format("%Y-%m-%d")
+        ));
+        widgets
+            .tracking_projects
+            .set_text(&lines('+', &report.projects));
+        widgets
+            .tracking_contexts
+            .set_text(&lines('@', &report.contexts));
+    }
+
+    fn periods(
+        periods: &[crate::tasks::dashboard::Period],
+        format: &str,
+    ) -> (Vec<String>, [(Vec<usize>, crate::widgets::chart::Color); 2]) {
+        use crate::widgets::chart::{BLUE, GREEN};
+
+        let labels = periods
+            .iter()
+            .map(|x| x.date.format(format).to_string())
+            .collect();
+        let created = periods.iter().map(|x| x.created).collect();
+        let completed = periods.iter().map(|x| x.completed).collect();
+
+        (labels, [(created, BLUE), (completed, GREEN)])
+    }
+
+    /// Sets the draw function of `chart` from the dashboard.
+    fn draw<F>(&self, chart: &gtk::DrawingArea, f: F)
+    where
+        F: Fn(
+                &crate::tasks::dashboard::Dashboard,
+                &gtk::cairo::Context,
+                f64,
+                f64,
+            ) -> Result<(), gtk::cairo::Error>
+            + 'static,
+    {
+        let dashboard = self.dashboard.clone();
+
+        chart.set_draw_func(move |_, context, width, height| {
+            f(&dashboard.borrow(), context, width.into(), height.into()).ok();
+        });
+    }
+}
+
+#[relm4::component(pub)]
+impl relm4::Component for Model {
+    type CommandOutput = ();
+    type Init = ();
+    type Input = Msg;
+    type Output = ();
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        _sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        use crate::widgets::chart;
+
+        let model = Self {
+            dashboard: Default::default(),
+        };
+
+        let widgets = view_output!();
+
+        model.draw(&widgets.days, |dashboard, context, width, height| {
+            let (labels, series) = Self::periods(&dashboard.days, "%d");
+            chart::bars(context, width, height, &labels, &series)
+        });
+        model.draw(&widgets.weeks, |dashboard, context, width, height| {
+            let (labels, series) = Self::periods(&dashboard.weeks, "%m-%d");
+            chart::bars(context, width, height, &labels, &series)
+        });
+        model.draw(&widgets.overdue, |dashboard, context, width, height| {
+            let (labels, values): (Vec<_>, Vec<_>) = dashboard
+                .overdue
+                .iter()
+                .map(|(date, count)| (date.format("%m-%d").to_string(), *count))
+                .unzip();
+            chart::lines(context, width, height, &labels, &[(values, chart::RED)])
+        });
+        model.draw(&widgets.priorities, |dashboard, context, width, height| {
+            chart::hbars(context, width, height, &dashboard.priorities, chart::BLUE)
+        });
+        model.draw(&widgets.contexts, |dashboard, context, width, height| {
+            chart::hbars(context, width, height, &dashboard.contexts, chart::BLUE)
+        });
+        model.draw(&widgets.projects, |dashboard, context, width, height| {
+            chart::hbars(context, width, height, &dashboard.projects, chart::BLUE)
+        });
+
+        model.update_dashboard(&widgets);
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        _: relm4::ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        match msg {
+            Msg::Update => self.update_dashboard(widgets),
+        }
+    }
+
+    view! {
+        gtk::ScrolledWindow {
+            gtk::Box {
+                set_margin_bottom: 10,
+                set_margin_end: 10,
+                set_margin_start: 10,
+                set_margin_top: 10,
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 5,
+
+                gtk::Label {
+                    set_markup: "Tasks <span foreground=\"#449ae8\">created</span> and <span foreground=\"#4db366\">completed</span> by day",
+                    set_xalign: 0.,
+                },
+                #[name = "days"]
+                gtk::DrawingArea {
+                    set_content_height: 150,
+                },
+                gtk::Label {
+                    set_text: "By week",
+                    set_xalign: 0.,
+                },
+                #[name = "weeks"]
+                gtk::DrawingArea {
+                    set_content_height: 150,
+                },
+                gtk::Label {
+                    set_text: "Overdue tasks",
+                    set_xalign: 0.,
+                },
+                #[name = "overdue"]
+                gtk::DrawingArea {
+                    set_content_height: 150,
+                },
+                gtk::Box {
+                    set_homogeneous: true,
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 5,
+
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+
+                        gtk::Label {
+                            set_text: "Open tasks by priority",
+                        },
+                        #[name = "priorities"]
+                        gtk::DrawingArea {
+                            set_content_height: 200,
+                        },
+                    },
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+
+                        gtk::Label {
+                            set_text: "By context",
+                        },
+                        #[name = "contexts"]
+                        gtk::DrawingArea {
+                            set_content_height: 200,
+                        },
+                    },
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+
+                        gtk::Label {
+                            set_text: "By project",
+                        },
+                        #[name = "projects"]
+                        gtk::DrawingArea {
+                            set_content_height: 200,
+                        },
+                    },
+                },
+                gtk::Label {
+                    set_text: "Recurring tasks streaks",
+                    set_xalign: 0.,
+                },
+                #[name = "streaks"]
+                gtk::Label {
+                    set_xalign: 0.,
+                },
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 5,
+
+                    gtk::Label {
+                        set_text: "Time spent from",
+                    },
+                    #[name = "from"]
+                    gtk::Entry {
+                        set_placeholder_text: Some("YYYY-MM-DD"),
+                        set_width_chars: 10,
+                        connect_activate => Msg::Update,
+                    },
+                    gtk::Label {
+                        set_text: "to",
+                    },
+                    #[name = "to"]
+                    gtk::Entry {
+                        set_placeholder_text: Some("YYYY-MM-DD"),
+                        set_width_chars: 10,
+                        connect_activate => Msg::Update,
+                    },
+                },
+                #[name = "tracking_total"]
+                gtk::Label {
+                    set_xalign: 0.,
+                },
+                gtk::Box {
+                    set_homogeneous: true,
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 5,
+
+                    #[name = "tracking_projects"]
+                    gtk::Label {
+                        set_valign: gtk::Align::Start,
+                        set_xalign: 0.,
+                    },
+                    #[name = "tracking_contexts"]
+                    gtk::Label {
+                        set_valign: gtk::Align::Start,
+                        set_xalign: 0.,
+                    },
+                },
+            },
+        }
+    }
+}
@@ -2,6 +2,10 @@ pub fn today() -> chrono::NaiveDate {
     chrono::Local::now().date_naive()
 }
 
+pub fn now() -> chrono::NaiveDateTime {
+    chrono::Local::now().naive_local()
+}
+
 pub fn from_glib(value: gtk::glib::DateTime) -> chrono::NaiveDate {
     let y = value.year();
     let m = value.month() as u32;
@@ -9,3 +13,129 @@ pub fn from_glib(value: gtk::glib::DateTime) -> chrono::NaiveDate {
 
     chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap()
 }
+
+/// Parses a time of day written as `14:30` or `14h30`.
+pub fn parse_time(value: &str) -> Option<chrono::NaiveTime> {
+    let value = value.trim().replace('h', ":");
+    let value = if value.ends_with(':') {
+        format!("{value}00")
+    } else {
+        value
+    };
+
+    chrono::NaiveTime::parse_from_str(&value, "%H:%M").ok()
+}
+
+pub fn format_time(time: chrono::NaiveTime) -> String {
+    time.format("%H:%M").to_string()
+}
+
+/// Longest duration accepted, so adding it to a date can't overflow.
+const MAX_DURATION: chrono::Duration = chrono::Duration::days(10 * 366);
+
+/// Parses a duration like `45m`, `2h`, `1h30m` or `1d`, a bare number is read
+/// as minutes. Negative durations and ones above ten years are rejected.
+pub fn parse_duration(value: &str) -> Option<chrono::Duration> {
+    let value = value.trim();
+
+    if value.is_empty() {
+        return None;
+    }
+
+    if value.chars().all(|c| c.is_ascii_digit()) {
+        return chrono::Duration::try_minutes(value.parse().ok()?).filter(|x| *x <= MAX_DURATION);
+    }
+
+    let mut duration = chrono::Duration::zero();
+    let mut number = String::new();
+
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let n = number.parse::<i64>().ok()?;
+        number.clear();
+
+        let part = match c {
+            'd' => chrono::Duration::try_days(n)?,
+            'h' => chrono::Duration::try_hours(n)?,
+            'm' => chrono::Duration::try_minutes(n)?,
+            _ => return None,
+        };
+        duration = duration.checked_add(&part)?;
+    }
+
+    if number.is_empty() {
+        Some(duration).filter(|x| *x <= MAX_DURATION)
+    } else {
+        None
+    }
+}
+
+pub fn format_duration(duration: chrono::Duration) -> String {
+    let hours = duration.num_hours();
+    let minutes = duration.num_minutes() % 60;
+
+    match (hours, minutes) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h{m}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_time() {
+        let time = chrono::NaiveTime::from_hms_opt(14, 30, 0);
+
+        assert_eq!(super::parse_time("14:30"), time);
+        assert_eq!(super::parse_time("14h30"), time);
+        assert_eq!(
+            super::parse_time("9h"),
+            chrono::NaiveTime::from_hms_opt(9, 0, 0)
+        );
+        assert_eq!(super::parse_time("25:00"), None);
+    }
+
+    #[test]
+    fn parse_duration() {
+        assert_eq!(
+            super::parse_duration("45m"),
+            Some(chrono::Duration::minutes(45))
+        );
+        assert_eq!(
+            super::parse_duration("1h30m"),
+            Some(chrono::Duration::minutes(90))
+        );
+        assert_eq!(
+            super::parse_duration("90"),
+            Some(chrono::Duration::minutes(90))
+        );
+        assert_eq!(super::parse_duration("1h30"), None);
+        assert_eq!(super::parse_duration("1w"), None);
+        assert_eq!(
+            super::parse_duration("3660d"),
+            Some(chrono::Duration::days(3660))
+        );
+        assert_eq!(super::parse_duration("3661d"), None);
+        assert_eq!(super::parse_duration("-5"), None);
+        assert_eq!(super::parse_duration("+5"), None);
+        assert_eq!(super::parse_duration("200000000000000"), None);
+        assert_eq!(super::parse_duration("99999999999d"), None);
+        assert_eq!(super::parse_duration("99999999999999999999m"), None);
+        assert_eq!(super::parse_duration("100000000000d100000000000d"), None);
+    }
+
+    #[test]
+    fn format_duration() {
+        assert_eq!(super::format_duration(chrono::Duration::minutes(45)), "45m");
+        assert_eq!(super::format_duration(chrono::Duration::minutes(120)), "2h");
+        assert_eq!(
+            super::format_duration(chrono::Duration::minutes(90)),
+            "1h30m"
+        );
+    }
+}
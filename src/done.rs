@@ -10,7 +10,7 @@ pub struct Model {
 }
 
 impl Model {
-    fn tasks() -> Vec<crate::tasks::Task> {
+    pub fn tasks() -> Vec<crate::tasks::Task> {
         let list = crate::application::tasks();
         let preferences = crate::application::preferences();
 
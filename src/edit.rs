@@ -3,10 +3,13 @@ use relm4::ComponentController as _;
 
 #[derive(Debug)]
 pub enum MsgInput {
+    Attach(Vec<std::path::PathBuf>),
+    Delete,
     Ok,
     Set(Box<crate::tasks::Task>),
     UpdateDate(DateType, Option<chrono::NaiveDate>),
     UpdateKeywords(std::collections::BTreeMap<String, String>),
+    UpdateNote(String),
     UpdatePriority(todo_txt::Priority),
     UpdateRecurrence(Option<todo_txt::task::Recurrence>),
 }
@@ -14,14 +17,18 @@ pub enum MsgInput {
 #[derive(Debug)]
 pub enum MsgOutput {
     Cancel,
+    Delete(Box<crate::tasks::Task>),
     Done(Box<crate::tasks::Task>),
 }
 
 pub struct Model {
+    /// Files dropped on the panel, copied when the task is saved.
+    attachments: Vec<(std::path::PathBuf, String)>,
     created: relm4::Controller<crate::widgets::calendar::Model>,
     due: relm4::Controller<crate::widgets::calendar::Model>,
     finish: relm4::Controller<crate::widgets::calendar::Model>,
     keywords: relm4::Controller<crate::widgets::keywords::Model>,
+    note: relm4::Controller<crate::widgets::note::Model>,
     priority: relm4::Controller<crate::widgets::priority::Model>,
     recurrence: relm4::Controller<crate::widgets::recurrence::Model>,
     threshold: relm4::Controller<crate::widgets::calendar::Model>,
@@ -41,6 +48,103 @@ impl Model {
             }
         }
     }
+
+    fn update_keywords(&mut self, keywords: std::collections::BTreeMap<String, String>) {
+        let special = self
+            .task
+            .tags
+            .iter()
+            .filter(|(k, _)| crate::tasks::Task::SPECIAL_TAGS.contains(&k.as_str()))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<Vec<_>>();
+
+        self.task.tags = keywords;
+        self.task.tags.extend(special);
+    }
+
+    /// Returns `false`, with the invalid entries marked, if a value can't be
+    /// parsed.
+    fn update_time(&mut self, widgets: &ModelWidgets) -> bool {
+        let time = widgets.time.text();
+
+        let time_valid = match crate::date::parse_time(&time) {
+            Some(time) => {
+                self.task.set_due_time(Some(time));
+                true
+            }
+            None if time.is_empty() => {
+                self.task.set_due_time(None);
+                true
+            }
+            None => {
+                log::warn!("Invalid time '{time}', expected HH:MM");
+                false
+            }
+        };
+
+        let duration = widgets.duration.text();
+
+        let duration_valid = match crate::date::parse_duration(&duration) {
+            Some(duration) => {
+                self.task.set_duration(Some(duration));
+                true
+            }
+            None if duration.is_empty() => {
+                self.task.set_duration(None);
+                true
+            }
+            None => {
+                log::warn!("Invalid duration '{duration}', expected something like 1h30m");
+                false
+            }
+        };
+
+        let estimate = widgets.estimate.text();
+
+        let estimate_valid = match crate::date::parse_duration(&estimate) {
+            Some(estimate) => {
+                self.task.set_estimate(Some(estimate));
+                true
+            }
+            None if estimate.is_empty() => {
+                self.task.set_estimate(None);
+                true
+            }
+            None => {
+                log::warn!("Invalid estimate '{estimate}', expected something like 1h30m");
+                false
+            }
+        };
+
+        let remind = widgets.remind.text().trim().to_string();
+        let previous = self.task.remind().cloned();
+
+        self.task
+            .set_remind((!remind.is_empty()).then_some(remind.clone()));
+
+        let remind_valid = remind.is_empty() || self.task.remind_at().is_some();
+
+        if !remind_valid {
+            log::warn!("Invalid reminder '{remind}', expected HH:MM or YYYY-MM-DDTHH:MM");
+            self.task.set_remind(previous);
+        }
+
+        Self::mark(&widgets.time, time_valid);
+        Self::mark(&widgets.duration, duration_valid);
+        Self::mark(&widgets.estimate, estimate_valid);
+        Self::mark(&widgets.remind, remind_valid);
+
+        time_valid && duration_valid && estimate_valid && remind_valid
+    }
+
+    /// Adds the `error` class to an entry whose text is invalid.
+    fn mark(entry: &gtk::Entry, valid: bool) {
+        if valid {
+            entry.remove_css_class("error");
+        } else {
+            entry.add_css_class("error");
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -76,7 +180,7 @@ impl relm4::Component for Model {
             });
 
         let keywords = crate::widgets::keywords::Model::builder()
-            .launch(init.tags.clone())
+            .launch(init.keywords())
             .forward(sender.input_sender(), |output| match output {
                 crate::widgets::keywords::MsgOutput::Updated(keywords) => {
                     MsgInput::UpdateKeywords(keywords)
@@ -91,6 +195,13 @@ impl relm4::Component for Model {
                 }
             });
 
+        let note = crate::widgets::note::Model::builder().launch(()).forward(
+            sender.input_sender(),
+            |output| match output {
+                crate::widgets::note::MsgOutput::Updated(note) => MsgInput::UpdateNote(note),
+            },
+        );
+
         let priority = crate::widgets::priority::Model::builder()
             .launch(init.priority.clone())
             .forward(sender.input_sender(), |output| match output {
@@ -116,11 +227,13 @@ impl relm4::Component for Model {
             });
 
         let model = Self {
+            attachments: Vec::new(),
             created,
             due,
             finish,
             threshold,
             keywords,
+            note,
             priority,
             task: init,
             recurrence,
@@ -128,6 +241,14 @@ impl relm4::Component for Model {
 
         let widgets = view_output!();
 
+        crate::widgets::completion::attach(&widgets.subject, "+@#", || {
+            crate::tasks::completion::tags(&crate::application::tasks())
+        });
+
+        root.add_controller(crate::widgets::task::file_drop_target(move |files| {
+            sender.input(MsgInput::Attach(files));
+        }));
+
         relm4::ComponentParts { model, widgets }
     }
 
@@ -141,13 +262,35 @@ impl relm4::Component for Model {
         use MsgInput::*;
 
         match msg {
+            Attach(files) => {
+                self.attachments
+                    .extend(crate::tasks::attachment::add(&mut self.task, &files));
+                self.note.emit(crate::widgets::note::MsgInput::Set(
+                    self.task.note.content().unwrap_or_default(),
+                ));
+            }
+            Delete => {
+                sender
+                    .output(MsgOutput::Delete(Box::new(self.task.clone())))
+                    .ok();
+            }
             Ok => {
-                let start = widgets.buffer.start_iter();
-                let end = widgets.buffer.end_iter();
-                self.task.note = widgets.buffer.text(&start, &end, true).to_string().into();
                 self.task.subject = widgets.subject.text().to_string();
                 self.task.flagged = widgets.flagged.is_active();
 
+                if !self.update_time(widgets) {
+                    return;
+                }
+
+                let attachments = crate::tasks::attachment::linked(&self.task, &self.attachments);
+
+                if let Err(err) = crate::tasks::attachment::copy(&attachments) {
+                    log::error!("{err}");
+                    return;
+                }
+
+                self.attachments.clear();
+
                 sender
                     .output(MsgOutput::Done(Box::new(self.task.clone())))
                     .ok();
@@ -155,9 +298,40 @@ impl relm4::Component for Model {
             Set(task) => {
                 widgets.subject.set_text(&task.subject);
                 widgets.flagged.set_active(task.flagged);
+                widgets.time.set_text(
+                    &task
+                        .due_time()
+                        .map(crate::date::format_time)
+                        .unwrap_or_default(),
+                );
+                widgets.duration.set_text(
+                    &task
+                        .duration()
+                        .map(crate::date::format_duration)
+                        .unwrap_or_default(),
+                );
+                widgets.estimate.set_text(
+                    &task
+                        .estimate()
+                        .map(crate::date::format_duration)
+                        .unwrap_or_default(),
+                );
                 widgets
-                    .buffer
-                    .set_text(&task.note.content().unwrap_or_default());
+                    .remind
+                    .set_text(task.remind().map(String::as_str).unwrap_or_default());
+
+                for entry in [
+                    &widgets.time,
+                    &widgets.duration,
+                    &widgets.estimate,
+                    &widgets.remind,
+                ] {
+                    Self::mark(entry, true);
+                }
+
+                self.note.emit(crate::widgets::note::MsgInput::Set(
+                    task.note.content().unwrap_or_default(),
+                ));
 
                 self.created
                     .emit(crate::widgets::calendar::MsgInput::Set(task.create_date));
@@ -166,7 +340,7 @@ impl relm4::Component for Model {
                 self.finish
                     .emit(crate::widgets::calendar::MsgInput::Set(task.finish_date));
                 self.keywords
-                    .emit(crate::widgets::keywords::MsgInput::Set(task.tags.clone()));
+                    .emit(crate::widgets::keywords::MsgInput::Set(task.keywords()));
                 self.priority.emit(crate::widgets::priority::MsgInput::Set(
                     task.priority.clone(),
                 ));
@@ -174,9 +348,11 @@ impl relm4::Component for Model {
                     .emit(crate::widgets::calendar::MsgInput::Set(task.threshold_date));
 
                 self.task = *task;
+                self.attachments.clear();
             }
             UpdateDate(date_type, date) => self.update_date(date_type, date),
-            UpdateKeywords(keywords) => self.task.tags = keywords,
+            UpdateKeywords(keywords) => self.update_keywords(keywords),
+            UpdateNote(note) => self.task.note = note.into(),
             UpdatePriority(priority) => self.task.priority = priority,
             UpdateRecurrence(recurrence) => self.task.recurrence = recurrence,
         }
@@ -231,6 +407,45 @@ impl relm4::Component for Model {
                         append: model.created.widget(),
                     },
                 },
+                gtk::Frame {
+                    set_label: Some("Time"),
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 10,
+
+                        #[name = "time"]
+                        gtk::Entry {
+                            set_hexpand: true,
+                            set_placeholder_text: Some("At (14:30)"),
+                            set_tooltip_text: Some("Time of day the task is due"),
+
+                            connect_activate => MsgInput::Ok,
+                        },
+                        #[name = "duration"]
+                        gtk::Entry {
+                            set_hexpand: true,
+                            set_placeholder_text: Some("Duration (1h30m)"),
+
+                            connect_activate => MsgInput::Ok,
+                        },
+                        #[name = "estimate"]
+                        gtk::Entry {
+                            set_hexpand: true,
+                            set_placeholder_text: Some("Estimate (2h)"),
+                            set_tooltip_text: Some("Estimated work, summed by day in the agenda"),
+
+                            connect_activate => MsgInput::Ok,
+                        },
+                        #[name = "remind"]
+                        gtk::Entry {
+                            set_hexpand: true,
+                            set_placeholder_text: Some("Remind (09:00)"),
+                            set_tooltip_text: Some("Time of day on the due date, or a full date like 2042-01-01T09:00"),
+
+                            connect_activate => MsgInput::Ok,
+                        },
+                    },
+                },
                 gtk::Frame {
                     set_label: Some("Keywords"),
 
@@ -239,16 +454,7 @@ impl relm4::Component for Model {
                 gtk::Frame {
                     set_label: Some("Note"),
 
-                    #[name = "note"]
-                    gtk::TextView {
-                        set_hexpand: true,
-                        set_vexpand: true,
-                        set_size_request: (-1, 100),
-                        #[wrap(Some)]
-                        #[name = "buffer"]
-                        set_buffer = &gtk::TextBuffer {
-                        },
-                    },
+                    set_child: Some(model.note.widget()),
                 },
                 gtk::ActionBar {
                     pack_start = &gtk::Button {
@@ -264,6 +470,12 @@ impl relm4::Component for Model {
                             sender.output(MsgOutput::Cancel).ok();
                         },
                     },
+                    pack_end = &gtk::Button {
+                        add_css_class: "destructive-action",
+                        set_label: "Delete",
+
+                        connect_clicked => MsgInput::Delete,
+                    },
                 },
             },
         }
@@ -0,0 +1,88 @@
+const HEADER: &[&str] = &[
+    "subject",
+    "priority",
+    "due",
+    "threshold",
+    "created",
+    "completed",
+    "done",
+    "projects",
+    "contexts",
+    "tags",
+    "note",
+];
+
+pub fn render(report: &super::Report) -> String {
+    let sections = report.has_sections();
+    let mut header = HEADER.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+
+    if sections {
+        header.insert(0, "section".to_string());
+    }
+
+    let mut csv = record(&header);
+
+    for (section, task) in report.tasks() {
+        let mut fields = vec![
+            task.subject.clone(),
+            task.priority.to_string(),
+            super::date(task.due_date),
+            super::date(task.threshold_date),
+            super::date(task.create_date),
+            super::date(task.finish_date),
+            if task.finished { "x" } else { "" }.to_string(),
+            task.projects.join(" "),
+            task.contexts.join(" "),
+            task.hashtags.join(" "),
+            task.note.content().unwrap_or_default(),
+        ];
+
+        if sections {
+            fields.insert(0, section.to_string());
+        }
+
+        csv.push_str(&record(&fields));
+    }
+
+    csv
+}
+
+fn record(fields: &[String]) -> String {
+    let fields = fields.iter().map(|x| field(x)).collect::<Vec<_>>();
+
+    format!("{}\r\n", fields.join(","))
+}
+
+fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn render() {
+        use std::str::FromStr as _;
+
+        let mut task =
+            crate::tasks::Task::from_str("(B) Call \"Bob\", later +work due:2042-01-05").unwrap();
+        task.note = "line 1\nline 2".to_string().into();
+
+        let report = crate::export::Report::new("Inbox", vec![task]);
+        let csv = super::render(&report);
+
+        assert_eq!(
+            csv,
+            "subject,priority,due,threshold,created,completed,done,projects,contexts,tags,note\r\n\
+             \"Call \"\"Bob\"\", later +work\",B,2042-01-05,,,,,work,,,\"line 1\nline 2\"\r\n"
+        );
+
+        let tasks = crate::import::Format::Csv.parse(&csv).unwrap();
+        assert_eq!(tasks[0].subject, "Call \"Bob\", later +work");
+        assert_eq!(tasks[0].priority, 1);
+        assert_eq!(tasks[0].note.content(), Some("line 1\nline 2".to_string()));
+    }
+}
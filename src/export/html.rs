@@ -0,0 +1,139 @@
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em auto; max-width: 50em; color: #222; }
+h1 { margin-bottom: 0; }
+.summary { color: gray; }
+ul { list-style: none; padding: 0; }
+li { border-bottom: 1px solid #ddd; padding: 0.5em 0; }
+li.done .subject { color: gray; text-decoration: line-through; }
+.priority { border-radius: 3px; color: white; background: #3465a4; padding: 0 0.3em; }
+.meta { color: gray; font-size: small; margin-left: 1.5em; }
+.note { margin: 0.5em 0 0 1.5em; padding-left: 0.5em; border-left: 3px solid #ddd; }
+";
+
+/// Standalone page, subjects reuse their escaped markup and notes are rendered
+/// from Markdown.
+pub fn render(report: &super::Report) -> String {
+    let total = report.tasks().count();
+    let done = report.tasks().filter(|(_, x)| x.finished).count();
+
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n<p class=\"summary\">Exported on {date}: {total} tasks, {done} done.</p>\n",
+        title = escape(&report.title),
+        date = crate::date::today().format("%Y-%m-%d"),
+    );
+
+    for (section, tasks) in &report.sections {
+        if !section.is_empty() {
+            html.push_str(&format!("<h2>{}</h2>\n", escape(section)));
+        }
+
+        html.push_str("<ul>\n");
+
+        for task in tasks {
+            html.push_str(&self::task(task));
+        }
+
+        html.push_str("</ul>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+
+    html
+}
+
+fn task(task: &crate::tasks::Task) -> String {
+    let mut html = format!(
+        "<li class=\"{}\">{} ",
+        if task.finished { "task done" } else { "task" },
+        if task.finished { "☑" } else { "☐" },
+    );
+
+    if !task.priority.is_lowest() {
+        html.push_str(&format!(
+            "<span class=\"priority\">{}</span> ",
+            task.priority
+        ));
+    }
+
+    html.push_str(&format!(
+        "<span class=\"subject\">{}</span>",
+        task.markup_subject()
+    ));
+
+    let mut meta = Vec::new();
+
+    if task.flagged {
+        meta.push("flagged".to_string());
+    }
+    if let Some(due) = task.due_date {
+        meta.push(format!("due {}", super::date(Some(due))));
+    }
+    if let Some(threshold) = task.threshold_date {
+        meta.push(format!("deferred until {}", super::date(Some(threshold))));
+    }
+    if let Some(finish) = task.finish_date {
+        meta.push(format!("completed {}", super::date(Some(finish))));
+    }
+
+    if !meta.is_empty() {
+        html.push_str(&format!("<div class=\"meta\">{}</div>", meta.join(" · ")));
+    }
+
+    if let Some(note) = task.note.content() {
+        html.push_str(&format!("<div class=\"note\">{}</div>", self::note(&note)));
+    }
+
+    html.push_str("</li>\n");
+
+    html
+}
+
+/// Raw HTML of the note is displayed as text.
+fn note(content: &str) -> String {
+    use pulldown_cmark::Event;
+
+    let parser =
+        pulldown_cmark::Parser::new_ext(content, crate::tasks::markup::options()).map(|event| {
+            match event {
+                Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+                event => event,
+            }
+        });
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn render() {
+        use std::str::FromStr as _;
+
+        let mut task =
+            crate::tasks::Task::from_str("(A) Ship <b> +release due:2042-01-05").unwrap();
+        task.note = "Some *notes* <script>alert(1)</script>\n\n[link](https://example.org)"
+            .to_string()
+            .into();
+
+        let report = crate::export::Report::new("Q&A", vec![task]);
+        let html = super::render(&report);
+
+        assert!(html.contains("<title>Q&amp;A</title>"));
+        assert!(html.contains(
+            "<li class=\"task\">☐ <span class=\"priority\">A</span> <span class=\"subject\">Ship &lt;b&gt; <b>+release</b></span><div class=\"meta\">due 2042-01-05</div>"
+        ));
+        assert!(html.contains(
+            "<div class=\"note\"><p>Some <em>notes</em> &lt;script&gt;alert(1)&lt;/script&gt;</p>\n<p><a href=\"https://example.org\">link</a></p>\n</div>"
+        ));
+    }
+}
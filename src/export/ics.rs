@@ -0,0 +1,309 @@
+const PRODID: &str = "-//effitask//effitask//EN";
+
+/// Path of the calendar regenerated after each write of the task list.
+pub fn feed() -> Option<String> {
+    std::env::var("EFFITASK_ICS_FILE").ok()
+}
+
+pub fn tasks(tasks: &[crate::tasks::Task]) -> Vec<crate::tasks::Task> {
+    tasks.iter().filter(|x| is_exported(x)).cloned().collect()
+}
+
+fn is_exported(task: &crate::tasks::Task) -> bool {
+    !task.finished && task.due_date.is_some()
+}
+
+/// Gives a unique `uid:` tag to the exported tasks without one, or sharing it
+/// with a previous task, so calendars update their events when tasks are
+/// edited. Returns whether a task changed.
+pub fn assign_uids(tasks: &mut [crate::tasks::Task]) -> bool {
+    let used = tasks
+        .iter()
+        .filter_map(|x| x.uid().map(str::to_string))
+        .collect::<std::collections::HashSet<_>>();
+    let mut seen = std::collections::HashSet::new();
+    let seed = chrono::Utc::now().timestamp_micros();
+    let mut changed = false;
+
+    for task in tasks.iter_mut().filter(|x| is_exported(x)) {
+        if let Some(uid) = task.uid()
+            && seen.insert(uid.to_string())
+        {
+            continue;
+        }
+
+        let uid = (0..)
+            .map(|n| hash(&format!("{seed} {} {n} {task}", task.id)))
+            .find(|x| !used.contains(x) && !seen.contains(x))
+            .unwrap_or_default();
+
+        task.set_uid(&uid);
+        seen.insert(uid);
+        changed = true;
+    }
+
+    changed
+}
+
+/// Every task is written as a `VTODO` for task managers and as a `VEVENT` for
+/// calendar applications.
+pub fn calendar(tasks: &[crate::tasks::Task]) -> String {
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{PRODID}"),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for task in tasks {
+        lines.extend(todo(task, &stamp));
+        lines.extend(event(task, &stamp));
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|x| fold(x)).collect::<Vec<_>>().join("")
+}
+
+fn todo(task: &crate::tasks::Task, stamp: &str) -> Vec<String> {
+    let mut lines = vec![
+        "BEGIN:VTODO".to_string(),
+        format!("UID:{}-todo@effitask", uid(task)),
+        format!("DTSTAMP:{stamp}"),
+    ];
+
+    lines.extend(common(task));
+
+    // RRULE requires a DTSTART, the due date is used without threshold date
+    if let Some(due) = task.due_date {
+        match task.threshold_date.filter(|x| *x <= due) {
+            Some(threshold) => {
+                let time = task.due_time().map(|_| chrono::NaiveTime::MIN);
+                lines.push(datetime("DTSTART", threshold, time));
+            }
+            None => lines.push(datetime("DTSTART", due, task.due_time())),
+        }
+
+        lines.push(datetime("DUE", due, task.due_time()));
+    }
+
+    if !task.priority.is_lowest() {
+        let priority = u8::from(task.priority.clone()).min(8) + 1;
+        lines.push(format!("PRIORITY:{priority}"));
+    }
+
+    lines.push("STATUS:NEEDS-ACTION".to_string());
+    lines.push("END:VTODO".to_string());
+
+    lines
+}
+
+fn event(task: &crate::tasks::Task, stamp: &str) -> Vec<String> {
+    let Some(due) = task.due_date else {
+        return Vec::new();
+    };
+
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}-event@effitask", uid(task)),
+        format!("DTSTAMP:{stamp}"),
+    ];
+
+    lines.extend(common(task));
+
+    match task.due_datetime().zip(task.due_time()) {
+        Some((start, _)) => {
+            let end = start + task.duration().unwrap_or(chrono::Duration::hours(1));
+
+            lines.push(datetime("DTSTART", start.date(), Some(start.time())));
+            lines.push(datetime("DTEND", end.date(), Some(end.time())));
+        }
+        None => {
+            lines.push(datetime("DTSTART", due, None));
+            lines.push(datetime("DTEND", due + chrono::Duration::days(1), None));
+        }
+    }
+
+    lines.push("TRANSP:TRANSPARENT".to_string());
+    lines.push("END:VEVENT".to_string());
+
+    lines
+}
+
+fn common(task: &crate::tasks::Task) -> Vec<String> {
+    let mut lines = vec![format!("SUMMARY:{}", escape(&task.subject))];
+
+    if let Some(create_date) = task.create_date {
+        lines.push(datetime("CREATED", create_date, None));
+    }
+
+    if !task.projects.is_empty() {
+        let categories = task
+            .projects
+            .iter()
+            .map(|x| escape(x))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        lines.push(format!("CATEGORIES:{categories}"));
+    }
+
+    if let Some(ref recurrence) = task.recurrence {
+        lines.push(format!("RRULE:{}", rrule(recurrence)));
+    }
+
+    if let Some(note) = task.note.content() {
+        lines.push(format!("DESCRIPTION:{}", escape(&note)));
+    }
+
+    lines
+}
+
+fn datetime(name: &str, date: chrono::NaiveDate, time: Option<chrono::NaiveTime>) -> String {
+    match time {
+        Some(time) => format!("{name}:{}T{}", date.format("%Y%m%d"), time.format("%H%M%S")),
+        None => format!("{name};VALUE=DATE:{}", date.format("%Y%m%d")),
+    }
+}
+
+fn rrule(recurrence: &todo_txt::task::Recurrence) -> String {
+    let freq = match recurrence.period {
+        todo_txt::task::Period::Day => "DAILY",
+        todo_txt::task::Period::Week => "WEEKLY",
+        todo_txt::task::Period::Month => "MONTHLY",
+        todo_txt::task::Period::Year => "YEARLY",
+    };
+
+    format!("FREQ={freq};INTERVAL={}", recurrence.num)
+}
+
+/// The `uid:` tag set by [`assign_uids`], a hash of the task line for tasks
+/// exported without one.
+fn uid(task: &crate::tasks::Task) -> String {
+    match task.uid() {
+        Some(uid) => uid.to_string(),
+        None => hash(&task.to_string()),
+    }
+}
+
+/// FNV-1a hash, stable across builds.
+fn hash(text: &str) -> String {
+    let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+
+    format!("{hash:016x}")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line to 75 octets, as required by RFC 5545.
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut len = 0;
+
+    for c in line.chars() {
+        if len + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            len = 1;
+        }
+
+        folded.push(c);
+        len += c.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn escape() {
+        assert_eq!(super::escape("a, b; c\\d\ne"), "a\\, b\\; c\\\\d\\ne");
+    }
+
+    #[test]
+    fn fold() {
+        let line = "x".repeat(100);
+        let folded = super::fold(&line);
+
+        assert_eq!(
+            folded,
+            format!("{}\r\n {}\r\n", "x".repeat(75), "x".repeat(25))
+        );
+    }
+
+    #[test]
+    fn calendar() {
+        use std::str::FromStr as _;
+
+        let task = crate::tasks::Task::from_str(
+            "(B) 2042-01-01 Call Bob +work due:2042-01-02 at:14:30 dur:30m rec:1w",
+        )
+        .unwrap();
+        let calendar = super::calendar(&[task]);
+
+        assert!(calendar.contains("BEGIN:VTODO\r\n"));
+        assert!(calendar.contains("DUE:20420102T143000\r\n"));
+        assert!(calendar.contains("PRIORITY:2\r\n"));
+        assert!(calendar.contains("CATEGORIES:work\r\n"));
+        assert!(calendar.contains("RRULE:FREQ=WEEKLY;INTERVAL=1\r\n"));
+        assert!(calendar.contains("BEGIN:VEVENT\r\n"));
+        assert!(calendar.contains("DTSTART:20420102T143000\r\n"));
+        assert!(calendar.contains("DTEND:20420102T150000\r\n"));
+        assert!(calendar.contains("CREATED;VALUE=DATE:20420101\r\n"));
+    }
+
+    #[test]
+    fn dtstart() {
+        let todo = |line: &str| super::todo(&crate::tasks::Task::from(line.to_string()), "");
+
+        assert!(
+            todo("Call due:2042-01-02 rec:1w").contains(&"DTSTART;VALUE=DATE:20420102".to_string())
+        );
+        assert!(
+            todo("Call t:2042-01-01 due:2042-01-02 at:14:30")
+                .contains(&"DTSTART:20420101T000000".to_string())
+        );
+    }
+
+    #[test]
+    fn uid() {
+        let mut tasks = [
+            "Call Bob due:2042-01-02",
+            "Call Bob due:2042-01-02",
+            "Pay rent due:2042-01-05 uid:0123456789abcdef",
+            "Copy due:2042-01-06 uid:0123456789abcdef",
+            "x Done due:2042-01-01",
+        ]
+        .map(|x| crate::tasks::Task::from(x.to_string()));
+
+        for (id, task) in tasks.iter_mut().enumerate() {
+            task.id = id;
+        }
+
+        assert!(super::assign_uids(&mut tasks));
+        assert!(tasks[0].uid().is_some());
+        assert_ne!(tasks[0].uid(), tasks[1].uid());
+        assert_eq!(tasks[2].uid(), Some("0123456789abcdef"));
+        assert_ne!(tasks[3].uid(), Some("0123456789abcdef"));
+        assert_eq!(tasks[4].uid(), None);
+        assert!(!super::assign_uids(&mut tasks));
+
+        let uid = super::uid(&tasks[0]);
+        tasks[0].touch(chrono::NaiveDate::from_ymd_opt(2042, 1, 3).unwrap());
+        tasks[0].add_spent(chrono::Duration::minutes(25));
+        assert_eq!(super::uid(&tasks[0]), uid);
+
+        assert_eq!(super::hash(""), "cbf29ce484222325");
+    }
+}
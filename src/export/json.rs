@@ -0,0 +1,106 @@
+use serde_json::Value;
+
+pub fn render(report: &super::Report) -> String {
+    let sections = report.has_sections();
+
+    let tasks = report
+        .tasks()
+        .map(|(section, task)| {
+            let mut fields = serde_json::Map::new();
+
+            if sections {
+                fields.insert("section".to_string(), string(section));
+            }
+
+            fields.extend(self::task(task));
+
+            Value::Object(fields)
+        })
+        .collect::<Vec<_>>();
+
+    let report = serde_json::json!({
+        "title": report.title,
+        "exported": super::date(Some(crate::date::today())),
+        "tasks": tasks,
+    });
+
+    format!("{report}\n")
+}
+
+fn task(task: &crate::tasks::Task) -> Vec<(String, Value)> {
+    let date = |date| match date {
+        Some(date) => string(&super::date(Some(date))),
+        None => Value::Null,
+    };
+    let list = |list: &[String]| Value::Array(list.iter().map(|x| string(x)).collect());
+
+    vec![
+        ("subject".to_string(), string(&task.subject)),
+        ("line".to_string(), string(&task.to_string())),
+        (
+            "priority".to_string(),
+            if task.priority.is_lowest() {
+                Value::Null
+            } else {
+                string(&task.priority.to_string())
+            },
+        ),
+        ("done".to_string(), Value::Bool(task.finished)),
+        ("flagged".to_string(), Value::Bool(task.flagged)),
+        ("created".to_string(), date(task.create_date)),
+        ("due".to_string(), date(task.due_date)),
+        ("threshold".to_string(), date(task.threshold_date)),
+        ("completed".to_string(), date(task.finish_date)),
+        ("projects".to_string(), list(&task.projects)),
+        ("contexts".to_string(), list(&task.contexts)),
+        ("hashtags".to_string(), list(&task.hashtags)),
+        (
+            "tags".to_string(),
+            Value::Object(
+                task.tags
+                    .iter()
+                    .map(|(k, v)| (k.clone(), string(v)))
+                    .collect(),
+            ),
+        ),
+        (
+            "note".to_string(),
+            task.note
+                .content()
+                .map(Value::String)
+                .unwrap_or(Value::Null),
+        ),
+    ]
+}
+
+fn string(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn render() {
+        use std::str::FromStr as _;
+
+        let task = crate::tasks::Task::from_str("(A) Review +work @office at:14:00").unwrap();
+        let report = crate::export::Report {
+            title: "Agenda".to_string(),
+            sections: vec![("Today".to_string(), vec![task])],
+        };
+
+        let json = super::render(&report).parse::<serde_json::Value>().unwrap();
+        let task = &json.get("tasks").and_then(|x| x.as_array()).unwrap()[0];
+
+        assert_eq!(json.get("title").and_then(|x| x.as_str()), Some("Agenda"));
+        assert_eq!(task.get("section").and_then(|x| x.as_str()), Some("Today"));
+        assert_eq!(task.get("priority").and_then(|x| x.as_str()), Some("A"));
+        assert_eq!(
+            task.get("tags")
+                .and_then(|x| x.get("at"))
+                .and_then(|x| x.as_str()),
+            Some("14:00")
+        );
+        assert_eq!(task.get("due"), Some(&serde_json::Value::Null));
+    }
+}
@@ -0,0 +1,101 @@
+/// Writes a checklist readable by the Markdown import: the item text is the
+/// todo.txt line without completion and creation dates, the note is indented
+/// below it. Headings are underlined, so the import doesn't turn them into
+/// projects.
+pub fn render(report: &super::Report) -> String {
+    let mut markdown = format!("{}\n{}\n", heading(&report.title, '='), summary(report));
+
+    for (section, tasks) in &report.sections {
+        if !section.is_empty() {
+            markdown.push_str(&format!("\n{}", heading(section, '-')));
+        }
+
+        markdown.push('\n');
+
+        for task in tasks {
+            markdown.push_str(&item(task));
+        }
+    }
+
+    markdown
+}
+
+/// Backslash escapes the ASCII punctuation, as CommonMark allows.
+fn escape(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, c| {
+        if c.is_ascii_punctuation() {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+        escaped
+    })
+}
+
+/// Setext heading: the title underlined with `underline`.
+fn heading(title: &str, underline: char) -> String {
+    let title = escape(title);
+    let underline = underline.to_string().repeat(title.chars().count());
+
+    format!("{title}\n{underline}\n")
+}
+
+fn summary(report: &super::Report) -> String {
+    let total = report.tasks().count();
+    let done = report.tasks().filter(|(_, x)| x.finished).count();
+
+    format!(
+        "_Exported on {}: {total} tasks, {done} done._",
+        crate::date::today().format("%Y-%m-%d")
+    )
+}
+
+fn item(task: &crate::tasks::Task) -> String {
+    let mut line = task.clone();
+    line.finished = false;
+    line.finish_date = None;
+    line.create_date = None;
+    line.note = todo_txt::task::Note::None;
+
+    let mut item = format!("- [{}] {line}\n", if task.finished { 'x' } else { ' ' });
+
+    if let Some(note) = task.note.content() {
+        for line in note.lines() {
+            item.push_str(&format!("  {line}\n"));
+        }
+    }
+
+    item
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn render() {
+        use std::str::FromStr as _;
+
+        let mut task =
+            crate::tasks::Task::from_str("x 2042-01-02 2042-01-01 (A) Write +report").unwrap();
+        task.note = "First\n\nSecond".to_string().into();
+
+        let report = crate::export::Report {
+            title: "Inbox *today*".to_string(),
+            sections: vec![("Today".to_string(), vec![task])],
+        };
+
+        let markdown = super::render(&report);
+
+        assert!(markdown.starts_with("Inbox \\*today\\*\n===============\n\n_Exported on "));
+        assert!(
+            markdown
+                .ends_with("\nToday\n-----\n\n- [x] (A) Write +report\n  First\n  \n  Second\n")
+        );
+
+        let tasks = crate::import::Format::Markdown.parse(&markdown).unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert!(tasks[0].finished);
+        assert_eq!(tasks[0].subject, "Write +report");
+        assert_eq!(tasks[0].projects, vec!["report".to_string()]);
+        assert_eq!(tasks[0].note.content(), Some("First\n\nSecond".to_string()));
+    }
+}
@@ -0,0 +1,81 @@
+mod csv;
+mod html;
+pub mod ics;
+mod json;
+mod markdown;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Format {
+    Markdown,
+    Csv,
+    Json,
+    Html,
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::Html => "html",
+        }
+    }
+
+    pub fn render(&self, report: &Report) -> String {
+        match self {
+            Self::Markdown => markdown::render(report),
+            Self::Csv => csv::render(report),
+            Self::Json => json::render(report),
+            Self::Html => html::render(report),
+        }
+    }
+}
+
+/// Tasks of a page, grouped by section. Pages without sections use a single
+/// untitled one.
+pub struct Report {
+    pub title: String,
+    pub sections: Vec<(String, Vec<crate::tasks::Task>)>,
+}
+
+impl Report {
+    pub fn new(title: &str, mut tasks: Vec<crate::tasks::Task>) -> Self {
+        tasks.sort();
+        tasks.reverse();
+
+        Self {
+            title: title.to_string(),
+            sections: vec![(String::new(), tasks)],
+        }
+    }
+
+    fn tasks(&self) -> impl Iterator<Item = (&str, &crate::tasks::Task)> {
+        self.sections
+            .iter()
+            .flat_map(|(section, tasks)| tasks.iter().map(move |x| (section.as_str(), x)))
+    }
+
+    fn has_sections(&self) -> bool {
+        self.sections.iter().any(|(section, _)| !section.is_empty())
+    }
+}
+
+pub fn file_name(title: &str, format: Format) -> String {
+    let name = title
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+
+    format!("{}.{}", name.to_lowercase(), format.extension())
+}
+
+fn date(date: Option<chrono::NaiveDate>) -> String {
+    date.map(|x| x.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+pub fn write(path: &std::path::Path, content: &str) -> Result<(), String> {
+    std::fs::write(path, content)
+        .map_err(|err| format!("Unable to export to {}: {err}", path.display()))
+}
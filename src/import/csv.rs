@@ -0,0 +1,174 @@
+pub fn parse(content: &str) -> Result<Vec<crate::tasks::Task>, String> {
+    let mut records = records(content).into_iter();
+
+    let Some(header) = records.next() else {
+        return Ok(Vec::new());
+    };
+
+    let header = header
+        .iter()
+        .map(|x| x.trim().to_lowercase())
+        .collect::<Vec<_>>();
+    let column = |names: &[&str]| header.iter().position(|x| names.contains(&x.as_str()));
+
+    let Some(subject) = column(&["subject", "description", "title", "task", "name"]) else {
+        return Err("The CSV file needs a subject, description or title column".to_string());
+    };
+
+    let priority = column(&["priority"]);
+    let due = column(&["due", "due date", "due_date"]);
+    let threshold = column(&["threshold", "defer", "start", "start date"]);
+    let created = column(&["created", "create date", "creation date", "entry"]);
+    let projects = column(&["project", "projects", "list"]);
+    let contexts = column(&["context", "contexts"]);
+    let hashtags = column(&["tags", "tag", "hashtags", "labels"]);
+    let done = column(&["done", "completed", "status"]);
+    let note = column(&["note", "notes", "annotations", "comment", "comments"]);
+
+    let mut tasks = Vec::new();
+
+    for record in records {
+        let field = |n: Option<usize>| {
+            n.and_then(|n| record.get(n))
+                .map(|x| x.trim())
+                .filter(|x| !x.is_empty())
+        };
+
+        let Some(text) = field(Some(subject)) else {
+            continue;
+        };
+
+        let mut task = crate::tasks::Task::new();
+        task.subject = text.to_string();
+        task.priority = field(priority).and_then(self::priority).unwrap_or_default();
+        task.due_date = field(due).and_then(date);
+        task.threshold_date = field(threshold).and_then(date);
+        task.create_date = field(created).and_then(date);
+        task.projects = field(projects).map(list).unwrap_or_default();
+        task.contexts = field(contexts).map(list).unwrap_or_default();
+        task.hashtags = field(hashtags).map(list).unwrap_or_default();
+        task.finished = field(done).is_some_and(|x| {
+            ["x", "1", "yes", "true", "done", "completed"].contains(&x.to_lowercase().as_str())
+        });
+
+        if let Some(note) = field(note) {
+            task.note = note.to_string().into();
+        }
+
+        tasks.push(task);
+    }
+
+    Ok(tasks)
+}
+
+fn records(content: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted => (),
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+
+                if record.iter().any(|x| !x.is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+            }
+            c => field.push(c),
+        }
+    }
+
+    record.push(field);
+
+    if record.iter().any(|x| !x.is_empty()) {
+        records.push(record);
+    }
+
+    records
+}
+
+fn priority(value: &str) -> Option<todo_txt::Priority> {
+    match value.to_lowercase().as_str() {
+        "h" | "high" => Some(0.into()),
+        "m" | "medium" => Some(1.into()),
+        "l" | "low" => Some(2.into()),
+        value if value.len() == 1 => value.chars().next()?.try_into().ok(),
+        _ => None,
+    }
+}
+
+/// ISO 8601 dates only, `01/02/2042` is ambiguous.
+fn date(value: &str) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+                .ok()
+                .map(|x| x.date())
+        })
+}
+
+fn list(value: &str) -> Vec<String> {
+    value
+        .split([',', ';', ' '])
+        .map(|x| x.trim_start_matches(['+', '@', '#']))
+        .filter(|x| !x.is_empty())
+        .map(super::tag)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse() {
+        let tasks = super::parse(
+            "Title,Priority,Due Date,Project,Tags,Done,Notes\n\
+             \"Write report, draft\",H,2042-01-05,work,\"a; b\",no,\"first line\nsecond \"\"quoted\"\"\"\n\
+             \n\
+             Old one,,,,,x,\n",
+        )
+        .unwrap();
+
+        assert_eq!(tasks.len(), 2);
+
+        let task = &tasks[0];
+        assert_eq!(task.subject, "Write report, draft");
+        assert_eq!(task.priority, 0);
+        assert_eq!(task.due_date, chrono::NaiveDate::from_ymd_opt(2042, 1, 5));
+        assert_eq!(task.projects, vec!["work".to_string()]);
+        assert_eq!(task.hashtags, vec!["a".to_string(), "b".to_string()]);
+        assert!(!task.finished);
+        assert_eq!(
+            task.note.content(),
+            Some("first line\nsecond \"quoted\"".to_string())
+        );
+        assert!(tasks[1].finished);
+    }
+
+    #[test]
+    fn date() {
+        let date = chrono::NaiveDate::from_ymd_opt(2042, 1, 5);
+
+        assert_eq!(super::date("2042-01-05"), date);
+        assert_eq!(super::date("2042-01-05T14:30:00"), date);
+        assert_eq!(super::date("05/01/2042"), None);
+        assert_eq!(super::date("2042/01/05"), None);
+    }
+
+    #[test]
+    fn missing_subject() {
+        assert!(super::parse("a,b\n1,2\n").is_err());
+    }
+}
@@ -0,0 +1,110 @@
+/// Reads checklist items (`- [ ] task`, `* [x] done`), `#` headings become
+/// projects (nested headings give sub-projects) and indented text under an item
+/// becomes its note. Underlined headings, as exported, are ignored.
+pub fn parse(content: &str) -> Result<Vec<crate::tasks::Task>, String> {
+    use std::str::FromStr as _;
+
+    static ITEM: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
+        regex::Regex::new(r"^\s*[-*+]\s+\[(?P<done>[ xX])\]\s+(?P<text>.+)$").unwrap()
+    });
+    static HEADING: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
+        regex::Regex::new(r"^(?P<level>#{1,6})\s+(?P<title>.+?)\s*#*$").unwrap()
+    });
+
+    let mut tasks: Vec<crate::tasks::Task> = Vec::new();
+    let mut notes: Vec<Vec<String>> = Vec::new();
+    let mut headings: Vec<String> = Vec::new();
+    let mut in_item = false;
+
+    for line in content.lines() {
+        if let Some(caps) = HEADING.captures(line) {
+            headings.truncate(caps["level"].len() - 1);
+            headings.push(super::tag(&unescape(&caps["title"])));
+            in_item = false;
+        } else if let Some(caps) = ITEM.captures(line) {
+            let mut task = crate::tasks::Task::from_str(&caps["text"]).unwrap();
+
+            if &caps["done"] != " " {
+                task.finished = true;
+            }
+
+            if !headings.is_empty() {
+                let project = headings.join("\\");
+
+                if !task.projects.contains(&project) {
+                    task.projects.push(project);
+                }
+            }
+
+            tasks.push(task);
+            notes.push(Vec::new());
+            in_item = true;
+        } else if in_item && (line.trim().is_empty() || line.starts_with([' ', '\t'])) {
+            if let Some(note) = notes.last_mut() {
+                note.push(unindent(line).to_string());
+            }
+        } else {
+            in_item = false;
+        }
+    }
+
+    for (task, note) in tasks.iter_mut().zip(notes) {
+        let note = note.join("\n");
+        let note = note.trim_matches('\n');
+
+        if !note.is_empty() {
+            task.note = note.to_string().into();
+        }
+    }
+
+    Ok(tasks)
+}
+
+/// Removes the item indentation, blank lines become empty.
+fn unindent(line: &str) -> &str {
+    if line.trim().is_empty() {
+        return "";
+    }
+
+    line.strip_prefix("  ")
+        .or_else(|| line.strip_prefix('\t'))
+        .unwrap_or(line)
+}
+
+fn unescape(text: &str) -> String {
+    static ESCAPE: std::sync::LazyLock<regex::Regex> =
+        std::sync::LazyLock::new(|| regex::Regex::new(r"\\([[:punct:]])").unwrap());
+
+    ESCAPE.replace_all(text, "$1").into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse() {
+        let tasks = super::parse(
+            "# Work\n\
+             Some introduction\n\
+             ## Admin stuff\n\
+             - [ ] (A) Fill expenses due:2042-01-05\n\
+             \x20 receipts are in the drawer\n\
+             - [x] Book train\n\
+             \n\
+             # Home\\_garden\n\
+             * [ ] Water plants @garden\n",
+        )
+        .unwrap();
+
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].subject, "Fill expenses");
+        assert_eq!(tasks[0].priority, 0);
+        assert_eq!(tasks[0].projects, vec!["Work\\Admin-stuff".to_string()]);
+        assert_eq!(
+            tasks[0].note.content(),
+            Some("receipts are in the drawer".to_string())
+        );
+        assert!(tasks[1].finished);
+        assert_eq!(tasks[2].projects, vec!["Home_garden".to_string()]);
+        assert_eq!(tasks[2].contexts, vec!["garden".to_string()]);
+    }
+}
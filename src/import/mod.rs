@@ -0,0 +1,282 @@
+mod csv;
+mod markdown;
+mod taskwarrior;
+
+use adw::prelude::*;
+use relm4::ComponentController as _;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Taskwarrior,
+    Csv,
+    Markdown,
+}
+
+impl Format {
+    const ALL: [Self; 3] = [Self::Taskwarrior, Self::Csv, Self::Markdown];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Taskwarrior => "Taskwarrior JSON",
+            Self::Csv => "CSV",
+            Self::Markdown => "Markdown checklist",
+        }
+    }
+
+    fn from_path(path: &std::path::Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+
+        match extension.as_str() {
+            "json" => Some(Self::Taskwarrior),
+            "csv" => Some(Self::Csv),
+            "md" | "markdown" => Some(Self::Markdown),
+            _ => None,
+        }
+    }
+
+    pub fn parse(&self, content: &str) -> Result<Vec<crate::tasks::Task>, String> {
+        match self {
+            Self::Taskwarrior => taskwarrior::parse(content),
+            Self::Csv => csv::parse(content),
+            Self::Markdown => markdown::parse(content),
+        }
+    }
+}
+
+/// Makes a string usable as project, context or hashtag name.
+fn tag(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '_' | '-' | '\\') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub enum MsgInput {
+    Choose,
+    Close,
+    Import,
+    Open(std::path::PathBuf),
+    SetFormat(u32),
+}
+
+#[derive(Debug)]
+pub enum MsgOutput {
+    Import(Vec<crate::tasks::Task>),
+}
+
+pub struct Model {
+    format: Format,
+    path: Option<std::path::PathBuf>,
+    preview: relm4::Controller<crate::widgets::tasks::Model>,
+    tasks: Vec<crate::tasks::Task>,
+}
+
+impl Model {
+    fn choose(&self, root: &gtk::Window, sender: relm4::ComponentSender<Self>) {
+        let dialog = gtk::FileDialog::builder()
+            .title("Tasks to import")
+            .modal(true)
+            .build();
+
+        dialog.open(Some(root), None::<&gtk::gio::Cancellable>, move |result| {
+            if let Some(path) = result.ok().and_then(|x| x.path()) {
+                sender.input(MsgInput::Open(path));
+            }
+        });
+    }
+
+    fn load(&mut self, widgets: &ModelWidgets) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        widgets.file.set_text(&path.display().to_string());
+
+        let result = std::fs::read_to_string(path)
+            .map_err(|err| format!("Unable to read {}: {err}", path.display()))
+            .and_then(|content| self.format.parse(&content));
+
+        match result {
+            Ok(tasks) => {
+                widgets
+                    .summary
+                    .set_text(&format!("{} tasks will be imported", tasks.len()));
+                self.tasks = tasks;
+            }
+            Err(err) => {
+                widgets.summary.set_text(&err);
+                self.tasks = Vec::new();
+            }
+        }
+
+        widgets.import.set_sensitive(!self.tasks.is_empty());
+        self.preview
+            .emit(crate::widgets::tasks::MsgInput::Update(self.tasks.clone()));
+    }
+
+    fn reset(&mut self, widgets: &ModelWidgets) {
+        self.path = None;
+        self.tasks = Vec::new();
+
+        widgets.file.set_text("");
+        widgets
+            .summary
+            .set_text("Choose a file to preview its tasks");
+        widgets.import.set_sensitive(false);
+        self.preview
+            .emit(crate::widgets::tasks::MsgInput::Update(Vec::new()));
+    }
+}
+
+#[relm4::component(pub)]
+impl relm4::Component for Model {
+    type CommandOutput = ();
+    type Init = ();
+    type Input = MsgInput;
+    type Output = MsgOutput;
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        let preview = crate::widgets::tasks::Model::builder()
+            .launch(().into())
+            .detach();
+        preview.emit(crate::widgets::tasks::MsgInput::ReadOnly);
+
+        let formats = Format::ALL.iter().map(Format::name).collect::<Vec<_>>();
+
+        let mut model = Self {
+            format: Format::Taskwarrior,
+            path: None,
+            preview,
+            tasks: Vec::new(),
+        };
+
+        let widgets = view_output!();
+
+        model.reset(&widgets);
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        use MsgInput::*;
+
+        match msg {
+            Choose => self.choose(root, sender),
+            Close => {
+                self.reset(widgets);
+                root.set_visible(false);
+            }
+            Import => {
+                let tasks = std::mem::take(&mut self.tasks);
+                sender.output(MsgOutput::Import(tasks)).ok();
+                sender.input(Close);
+            }
+            Open(path) => {
+                if let Some(format) = Format::from_path(&path) {
+                    self.format = format;
+
+                    let position = Format::ALL.iter().position(|x| *x == format).unwrap();
+                    widgets.format.set_selected(position as u32);
+                }
+
+                self.path = Some(path);
+                self.load(widgets);
+            }
+            SetFormat(position) => match Format::ALL.get(position as usize) {
+                Some(format) if *format != self.format => {
+                    self.format = *format;
+                    self.load(widgets);
+                }
+                _ => (),
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            set_default_size: (700, 500),
+            set_hide_on_close: true,
+            set_modal: true,
+            set_title: Some("Import tasks"),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 10,
+                set_margin_bottom: 10,
+                set_margin_end: 10,
+                set_margin_start: 10,
+                set_margin_top: 10,
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 10,
+
+                    gtk::Button {
+                        set_icon_name: "document-open",
+                        set_label: "Choose file…",
+
+                        connect_clicked => MsgInput::Choose,
+                    },
+                    #[name = "file"]
+                    gtk::Label {
+                        set_ellipsize: gtk::pango::EllipsizeMode::Start,
+                        set_hexpand: true,
+                        set_xalign: 0.,
+                    },
+                    #[name = "format"]
+                    gtk::DropDown::from_strings(&formats) {
+                        set_tooltip_text: Some("Format"),
+
+                        connect_selected_notify[sender] => move |this| {
+                            sender.input(MsgInput::SetFormat(this.selected()));
+                        },
+                    },
+                },
+                #[name = "summary"]
+                gtk::Label {
+                    set_xalign: 0.,
+                },
+                gtk::Frame {
+                    set_vexpand: true,
+                    set_child: Some(model.preview.widget()),
+                },
+                gtk::ActionBar {
+                    pack_end = &gtk::Button {
+                        set_label: "Cancel",
+
+                        connect_clicked => MsgInput::Close,
+                    },
+                    #[name = "import"]
+                    pack_end = &gtk::Button {
+                        add_css_class: "suggested-action",
+                        set_label: "Import",
+
+                        connect_clicked => MsgInput::Import,
+                    },
+                },
+            },
+
+            connect_close_request[sender] => move |_| {
+                sender.input(MsgInput::Close);
+                gtk::glib::Propagation::Proceed
+            },
+        }
+    }
+}
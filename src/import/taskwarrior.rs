@@ -0,0 +1,186 @@
+pub fn parse(content: &str) -> Result<Vec<crate::tasks::Task>, String> {
+    parse_in(content, &chrono::Local)
+}
+
+/// Parses the export with dates converted to the `tz` timezone.
+fn parse_in<Tz: chrono::TimeZone>(
+    content: &str,
+    tz: &Tz,
+) -> Result<Vec<crate::tasks::Task>, String> {
+    let json = serde_json::from_str::<serde_json::Value>(content)
+        .map_err(|err| format!("Invalid Taskwarrior export: {err}"))?;
+
+    let Some(items) = json.as_array() else {
+        return Err("Invalid Taskwarrior export: expected an array of tasks".to_string());
+    };
+
+    Ok(items.iter().filter_map(|x| task(x, tz)).collect())
+}
+
+fn task<Tz: chrono::TimeZone>(item: &serde_json::Value, tz: &Tz) -> Option<crate::tasks::Task> {
+    let field = |name| item.get(name).and_then(serde_json::Value::as_str);
+    let datetime = |value| datetime(value, tz);
+
+    let status = field("status").unwrap_or("pending");
+
+    if status == "deleted" {
+        return None;
+    }
+
+    let mut task = crate::tasks::Task::new();
+    task.subject = field("description")?.to_string();
+    task.finished = status == "completed";
+    task.create_date = field("entry").and_then(datetime).map(|x| x.date());
+    task.finish_date = field("end").and_then(datetime).map(|x| x.date());
+
+    if let Some(due) = field("due").and_then(datetime) {
+        task.due_date = Some(due.date());
+
+        if due.time() != chrono::NaiveTime::MIN {
+            task.set_due_time(Some(due.time()));
+        }
+    }
+
+    task.threshold_date = field("wait")
+        .or_else(|| field("scheduled"))
+        .and_then(datetime)
+        .map(|x| x.date());
+
+    if let Some(project) = field("project") {
+        task.projects.push(super::tag(&project.replace('.', "\\")));
+    }
+
+    if let Some(tags) = item.get("tags").and_then(serde_json::Value::as_array) {
+        task.hashtags = tags
+            .iter()
+            .filter_map(serde_json::Value::as_str)
+            .map(super::tag)
+            .collect();
+    }
+
+    task.priority = match field("priority") {
+        Some("H") => 0.into(),
+        Some("M") => 1.into(),
+        Some("L") => 2.into(),
+        _ => todo_txt::Priority::lowest(),
+    };
+
+    task.recurrence = field("recur").and_then(recurrence);
+
+    if let Some(annotations) = item
+        .get("annotations")
+        .and_then(serde_json::Value::as_array)
+    {
+        let note = annotations
+            .iter()
+            .filter_map(|x| {
+                let description = x.get("description")?.as_str()?;
+                let date = x
+                    .get("entry")
+                    .and_then(serde_json::Value::as_str)
+                    .and_then(datetime)
+                    .map(|x| format!("{}: ", x.date()))
+                    .unwrap_or_default();
+
+                Some(format!("* {date}{description}"))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if !note.is_empty() {
+            task.note = note.into();
+        }
+    }
+
+    Some(task)
+}
+
+/// Taskwarrior dates are UTC timestamps like `20240105T143000Z`, converted to
+/// the `tz` timezone.
+fn datetime<Tz: chrono::TimeZone>(value: &str, tz: &Tz) -> Option<chrono::NaiveDateTime> {
+    let utc = chrono::NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ").ok()?;
+
+    Some(utc.and_utc().with_timezone(tz).naive_local())
+}
+
+fn recurrence(value: &str) -> Option<todo_txt::task::Recurrence> {
+    let rec = match value {
+        "daily" | "day" => "1d",
+        "weekly" | "week" => "1w",
+        "biweekly" | "fortnight" => "2w",
+        "monthly" | "month" => "1m",
+        "quarterly" => "3m",
+        "yearly" | "annual" | "year" => "1y",
+        _ => {
+            let num = value
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect::<String>();
+            let period = value[num.len()..].chars().next()?;
+
+            return format!("{}{period}", if num.is_empty() { "1" } else { &num })
+                .parse()
+                .ok();
+        }
+    };
+
+    rec.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse() {
+        let tasks = super::parse_in(
+            r#"[
+                {"description": "Plant tomatoes", "status": "pending", "project": "home.garden",
+                 "tags": ["outside"], "priority": "H", "due": "20420105T000000Z", "recur": "weekly",
+                 "annotations": [{"entry": "20420101T100000Z", "description": "buy seeds"}]},
+                {"description": "Removed", "status": "deleted"}
+            ]"#,
+            &chrono::Utc,
+        )
+        .unwrap();
+
+        assert_eq!(tasks.len(), 1);
+
+        let task = &tasks[0];
+        assert_eq!(task.subject, "Plant tomatoes");
+        assert_eq!(task.projects, vec!["home\\garden".to_string()]);
+        assert_eq!(task.hashtags, vec!["outside".to_string()]);
+        assert_eq!(task.priority, 0);
+        assert_eq!(task.due_date, chrono::NaiveDate::from_ymd_opt(2042, 1, 5));
+        assert_eq!(
+            task.recurrence.as_ref().map(ToString::to_string),
+            Some("1w".to_string())
+        );
+        assert_eq!(
+            task.note.content(),
+            Some("* 2042-01-01: buy seeds".to_string())
+        );
+    }
+
+    #[test]
+    fn local_midnight() {
+        // Midnight in UTC+2
+        let tz = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        let tasks = super::parse_in(
+            r#"[{"description": "Water", "entry": "20420101T230000Z", "due": "20420104T220000Z",
+                 "end": "20420104T221500Z", "status": "completed"}]"#,
+            &tz,
+        )
+        .unwrap();
+
+        let task = &tasks[0];
+        assert_eq!(
+            task.create_date,
+            chrono::NaiveDate::from_ymd_opt(2042, 1, 2)
+        );
+        assert_eq!(task.due_date, chrono::NaiveDate::from_ymd_opt(2042, 1, 5));
+        assert_eq!(task.due_time(), None);
+        assert_eq!(
+            task.finish_date,
+            chrono::NaiveDate::from_ymd_opt(2042, 1, 5)
+        );
+    }
+}
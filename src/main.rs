@@ -2,15 +2,23 @@
 use adw::prelude::*;
 mod agenda;
 mod application;
+mod dashboard;
 mod date;
 mod done;
 mod edit;
+mod export;
 mod filter;
 mod flag;
+mod import;
 mod inbox;
 mod logger;
+mod next;
+mod reminder;
+mod review;
 mod search;
 mod tasks;
+mod timer;
+mod today;
 mod widgets;
 
 use filter::Filter;
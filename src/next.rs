@@ -0,0 +1,159 @@
+use adw::prelude::*;
+
+#[derive(Debug)]
+pub enum Msg {
+    Blocked(bool),
+    Deferred(bool),
+    DueFirst(bool),
+    Update,
+}
+
+pub struct Model {
+    rules: crate::tasks::next::Rules,
+    /// Projects without next action.
+    stalled: Vec<String>,
+    tasks: relm4::Controller<crate::widgets::tasks::Model>,
+}
+
+impl Model {
+    fn next_actions(&self) -> Vec<(String, Option<crate::tasks::Task>)> {
+        let list = crate::application::tasks();
+        let preferences = crate::application::preferences();
+        let rules = crate::tasks::next::Rules {
+            hidden: !preferences.hidden,
+            ..self.rules
+        };
+
+        crate::tasks::next::next_actions(
+            &list.tasks,
+            &crate::tasks::project::load(),
+            crate::date::today(),
+            rules,
+        )
+    }
+
+    pub fn tasks(&self) -> Vec<crate::tasks::Task> {
+        self.next_actions()
+            .into_iter()
+            .filter_map(|(_, task)| task)
+            .collect()
+    }
+
+    fn update_tasks(&mut self) {
+        use relm4::ComponentController as _;
+
+        let (stalled, tasks): (Vec<_>, Vec<_>) = self
+            .next_actions()
+            .into_iter()
+            .partition(|(_, task)| task.is_none());
+
+        self.stalled = stalled.into_iter().map(|(name, _)| name).collect();
+        self.tasks.emit(crate::widgets::tasks::MsgInput::Update(
+            tasks.into_iter().filter_map(|(_, task)| task).collect(),
+        ));
+    }
+
+    fn stalled_markup(&self) -> String {
+        let projects = self
+            .stalled
+            .iter()
+            .map(|x| format!("<b>+{}</b>", gtk::glib::markup_escape_text(x)))
+            .collect::<Vec<_>>();
+
+        format!("No next action: {}", projects.join(", "))
+    }
+}
+
+#[relm4::component(pub)]
+impl relm4::SimpleComponent for Model {
+    type Init = ();
+    type Input = Msg;
+    type Output = crate::widgets::task::MsgOutput;
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        use relm4::Component as _;
+        use relm4::ComponentController as _;
+
+        let tasks = crate::widgets::tasks::Model::builder()
+            .launch(().into())
+            .forward(sender.output_sender(), std::convert::identity);
+        tasks.emit(crate::widgets::tasks::MsgInput::SortBy(|a, b| {
+            a.projects.cmp(&b.projects)
+        }));
+
+        let mut model = Self {
+            rules: Default::default(),
+            stalled: Vec::new(),
+            tasks,
+        };
+        model.update_tasks();
+
+        let widgets = view_output!();
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, _: relm4::ComponentSender<Self>) {
+        match msg {
+            Msg::Blocked(blocked) => self.rules.blocked = blocked,
+            Msg::Deferred(deferred) => self.rules.deferred = deferred,
+            Msg::DueFirst(due_first) => self.rules.due_first = due_first,
+            Msg::Update => (),
+        }
+
+        self.update_tasks();
+    }
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+
+            gtk::Box {
+                set_margin_end: 10,
+                set_margin_start: 10,
+                set_margin_top: 5,
+                set_orientation: gtk::Orientation::Horizontal,
+                set_spacing: 10,
+
+                gtk::CheckButton {
+                    set_active: model.rules.deferred,
+                    set_label: Some("Skip deferred tasks"),
+                    connect_toggled[sender] => move |this| {
+                        sender.input(Msg::Deferred(this.is_active()));
+                    },
+                },
+                gtk::CheckButton {
+                    set_active: model.rules.blocked,
+                    set_label: Some("Skip blocked tasks"),
+                    set_tooltip_text: Some("Tasks with dep:<id> while the task with id:<id> is open"),
+                    connect_toggled[sender] => move |this| {
+                        sender.input(Msg::Blocked(this.is_active()));
+                    },
+                },
+                gtk::CheckButton {
+                    set_active: model.rules.due_first,
+                    set_label: Some("Due date before priority"),
+                    connect_toggled[sender] => move |this| {
+                        sender.input(Msg::DueFirst(this.is_active()));
+                    },
+                },
+            },
+            gtk::Label {
+                add_css_class: "warning",
+                set_margin_end: 10,
+                set_margin_start: 10,
+                set_wrap: true,
+                set_xalign: 0.,
+                #[watch]
+                set_markup: &model.stalled_markup(),
+                #[watch]
+                set_visible: !model.stalled.is_empty(),
+            },
+            append: model.tasks.widget(),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use adw::prelude::*;
+
+const SNOOZE: chrono::Duration = chrono::Duration::minutes(10);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Event {
+    Due,
+    Threshold,
+    Remind,
+}
+
+impl Event {
+    fn title(&self) -> &'static str {
+        match self {
+            Self::Due => "Task due",
+            Self::Threshold => "Task available",
+            Self::Remind => "Reminder",
+        }
+    }
+}
+
+pub struct Scheduler {
+    last_check: chrono::NaiveDateTime,
+    /// Notified tasks by notification key, followed across edits.
+    notified: std::collections::HashMap<String, String>,
+    next_key: usize,
+    snoozed: Vec<(String, chrono::NaiveDateTime)>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        // Events passed before the launch aren't notified again on each start.
+        Self {
+            last_check: crate::date::now(),
+            notified: std::collections::HashMap::new(),
+            next_key: 0,
+            snoozed: Vec::new(),
+        }
+    }
+
+    pub fn check(&mut self, list: &crate::tasks::List) {
+        let now = crate::date::now();
+        let preferences = crate::application::preferences();
+
+        for key in self.prune(&list.tasks) {
+            relm4::main_application().withdraw_notification(&key);
+        }
+
+        for task in &list.tasks {
+            if task.finished || (task.hidden && !preferences.hidden) {
+                continue;
+            }
+
+            for (event, at) in Self::events(task) {
+                if at > self.last_check && at <= now {
+                    self.notify(task, event);
+                }
+            }
+        }
+
+        let (expired, snoozed) = self
+            .snoozed
+            .drain(..)
+            .partition::<Vec<_>, _>(|(_, at)| *at <= now);
+        self.snoozed = snoozed;
+
+        for (key, _) in expired {
+            if let Some(task) = self.find(&key) {
+                self.notify(&task, Event::Remind);
+            }
+        }
+
+        self.last_check = now;
+    }
+
+    pub fn snooze(&mut self, key: String) {
+        relm4::main_application().withdraw_notification(&key);
+        self.snoozed.push((key, crate::date::now() + SNOOZE));
+    }
+
+    pub fn find(&self, key: &str) -> Option<crate::tasks::Task> {
+        let line = self.notified.get(key)?;
+
+        crate::application::tasks()
+            .tasks
+            .iter()
+            .find(|x| x.to_string() == *line)
+            .cloned()
+    }
+
+    /// Keeps the pending notifications of `old` pointing to its edited version.
+    pub fn follow(&mut self, old: &crate::tasks::Task, new: &crate::tasks::Task) {
+        let old = old.to_string();
+
+        for line in self.notified.values_mut().filter(|x| **x == old) {
+            *line = new.to_string();
+        }
+    }
+
+    /// Forgets the notifications of finished or removed tasks, returning their
+    /// keys.
+    fn prune(&mut self, tasks: &[crate::tasks::Task]) -> Vec<String> {
+        let lines = tasks
+            .iter()
+            .filter(|x| !x.finished)
+            .map(ToString::to_string)
+            .collect::<std::collections::HashSet<_>>();
+
+        let pruned = self
+            .notified
+            .iter()
+            .filter(|(_, line)| !lines.contains(*line))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        self.notified.retain(|key, _| !pruned.contains(key));
+        self.snoozed.retain(|(key, _)| !pruned.contains(key));
+
+        pruned
+    }
+
+    fn key(&mut self, task: &crate::tasks::Task) -> String {
+        let line = task.to_string();
+
+        if let Some((key, _)) = self.notified.iter().find(|(_, x)| **x == line) {
+            return key.clone();
+        }
+
+        self.next_key += 1;
+        let key = format!("reminder-{}", self.next_key);
+        self.notified.insert(key.clone(), line);
+
+        key
+    }
+
+    fn events(task: &crate::tasks::Task) -> Vec<(Event, chrono::NaiveDateTime)> {
+        let mut events = Vec::new();
+
+        if let Some(due) = task.due_datetime() {
+            events.push((Event::Due, due));
+        }
+
+        if let Some(threshold) = task.threshold_date {
+            events.push((Event::Threshold, threshold.and_time(chrono::NaiveTime::MIN)));
+        }
+
+        if let Some(remind) = task.remind_at() {
+            events.push((Event::Remind, remind));
+        }
+
+        events
+    }
+
+    fn notify(&mut self, task: &crate::tasks::Task, event: Event) {
+        let key = self.key(task);
+        let mut body = task.subject.clone();
+
+        if let Some(time) = task.due_time() {
+            body.push_str(&format!(" at {}", crate::date::format_time(time)));
+        }
+
+        let notification = gtk::gio::Notification::new(event.title());
+        notification.set_body(Some(&body));
+        notification.set_default_action_and_target_value("app.edit", Some(&key.to_variant()));
+        notification.add_button_with_target_value("Snooze", "app.snooze", Some(&key.to_variant()));
+        notification.add_button_with_target_value(
+            "Complete",
+            "app.complete",
+            Some(&key.to_variant()),
+        );
+
+        relm4::main_application().send_notification(Some(&key), &notification);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Event;
+
+    #[test]
+    fn events() {
+        let datetime = |day, hour, minute| {
+            chrono::NaiveDate::from_ymd_opt(2042, 1, day)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+                .unwrap()
+        };
+        let task = crate::tasks::Task::from(
+            "Call t:2042-01-01 due:2042-01-02 at:14:30 remind:13:00".to_string(),
+        );
+
+        assert_eq!(
+            super::Scheduler::events(&task),
+            vec![
+                (Event::Due, datetime(2, 14, 30)),
+                (Event::Threshold, datetime(1, 0, 0)),
+                (Event::Remind, datetime(2, 13, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn prune() {
+        let task = |line: &str| crate::tasks::Task::from(line.to_string());
+        let mut scheduler = super::Scheduler::new();
+        let call = scheduler.key(&task("Call due:2042-01-02"));
+        let write = scheduler.key(&task("Write due:2042-01-02"));
+        let pay = scheduler.key(&task("Pay due:2042-01-02"));
+        scheduler
+            .snoozed
+            .push((pay.clone(), crate::date::now() + super::SNOOZE));
+
+        let mut pruned = scheduler.prune(&[
+            task("Call due:2042-01-02"),
+            task("x 2042-01-02 Write due:2042-01-02"),
+        ]);
+        pruned.sort();
+
+        assert_eq!(pruned, vec![write, pay]);
+        assert_eq!(scheduler.notified.keys().collect::<Vec<_>>(), vec![&call]);
+        assert!(scheduler.snoozed.is_empty());
+    }
+
+    #[test]
+    fn no_events() {
+        let task = crate::tasks::Task::from("Call".to_string());
+
+        assert!(super::Scheduler::events(&task).is_empty());
+    }
+}
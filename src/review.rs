@@ -0,0 +1,327 @@
+use adw::prelude::*;
+
+#[derive(Debug)]
+pub enum MsgInput {
+    Finish,
+    Next,
+    NextAction(String, String),
+    Previous,
+    ProjectDone(String),
+    Update,
+}
+
+#[derive(Debug)]
+pub enum MsgOutput {
+    Add(String),
+    Task(crate::widgets::task::MsgOutput),
+    Updated,
+}
+
+pub struct Model {
+    step: crate::tasks::review::Step,
+    tasks: relm4::Controller<crate::widgets::tasks::Model>,
+}
+
+impl Model {
+    /// Tasks of the current step.
+    pub fn tasks(&self) -> Vec<crate::tasks::Task> {
+        use crate::tasks::review::Step;
+
+        let today = crate::date::today();
+        let list = crate::application::tasks();
+
+        match self.step {
+            Step::Inbox => crate::inbox::Model::tasks(),
+            Step::Stale => crate::tasks::review::stale(&list.tasks, today),
+            Step::Projects => Vec::new(),
+            Step::Deferred => crate::tasks::review::deferred(&list.tasks, today),
+            Step::Completed => crate::tasks::review::completed(&list.tasks, today),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        format!("Review: {}", self.step.title())
+    }
+
+    fn update_step(&self, widgets: &ModelWidgets, sender: &relm4::ComponentSender<Self>) {
+        use crate::tasks::review::Step;
+        use relm4::ComponentController as _;
+
+        let index = self.step.index();
+
+        widgets.title.set_text(&format!(
+            "{}/{} · {}",
+            index + 1,
+            Step::ALL.len(),
+            self.step.title()
+        ));
+        widgets.description.set_text(self.step.description());
+        widgets
+            .previous
+            .set_sensitive(self.step.previous().is_some());
+        widgets.next.set_visible(self.step.next().is_some());
+        widgets.finish.set_visible(self.step.next().is_none());
+
+        let projects = self.step == Step::Projects;
+        widgets.projects_window.set_visible(projects);
+        self.tasks.widget().set_visible(!projects);
+
+        if projects {
+            self.update_projects(widgets, sender);
+        } else {
+            self.tasks
+                .emit(crate::widgets::tasks::MsgInput::Update(self.tasks()));
+        }
+    }
+
+    fn update_projects(&self, widgets: &ModelWidgets, sender: &relm4::ComponentSender<Self>) {
+        let list = crate::application::tasks();
+        let projects = crate::tasks::project::load();
+        let stalled =
+            crate::tasks::review::stalled_projects(&list.tasks, &projects, crate::date::today());
+
+        widgets.projects.remove_all();
+
+        if stalled.is_empty() {
+            let label = gtk::Label::new(Some("Every active project has a next action :)"));
+            label.set_margin_top(10);
+            widgets.projects.append(&label);
+        }
+
+        for name in stalled {
+            let summary = projects.get(&name).cloned().unwrap_or_default().summary();
+            widgets
+                .projects
+                .append(&Self::project_row(sender, &name, &summary));
+        }
+    }
+
+    fn project_row(sender: &relm4::ComponentSender<Self>, name: &str, summary: &str) -> gtk::Box {
+        let row = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+        row.set_margin_bottom(5);
+        row.set_margin_end(5);
+        row.set_margin_start(5);
+        row.set_margin_top(5);
+
+        let labels = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        labels.set_hexpand(true);
+
+        let title = gtk::Label::new(Some(name));
+        title.set_xalign(0.);
+        labels.append(&title);
+
+        let summary = gtk::Label::new(Some(summary));
+        summary.add_css_class("dim-label");
+        summary.set_xalign(0.);
+        labels.append(&summary);
+
+        row.append(&labels);
+
+        let entry = gtk::Entry::new();
+        entry.set_placeholder_text(Some("Next action"));
+        entry.set_width_chars(30);
+        {
+            let sender = sender.clone();
+            let name = name.to_string();
+
+            entry.connect_activate(move |this| {
+                sender.input(MsgInput::NextAction(name.clone(), this.text().to_string()));
+                this.set_text("");
+            });
+        }
+        row.append(&entry);
+
+        let done = gtk::Button::with_label("Done");
+        done.set_tooltip_text(Some("Mark the project as done"));
+        {
+            let sender = sender.clone();
+            let name = name.to_string();
+
+            done.connect_clicked(move |_| sender.input(MsgInput::ProjectDone(name.clone())));
+        }
+        row.append(&done);
+
+        row
+    }
+
+    fn next_action(&self, sender: &relm4::ComponentSender<Self>, name: &str, text: &str) {
+        let text = text.trim();
+
+        if text.is_empty() {
+            return;
+        }
+
+        sender
+            .output(MsgOutput::Add(format!("{text} +{name}")))
+            .ok();
+    }
+
+    fn project_done(&self, sender: &relm4::ComponentSender<Self>, name: &str) {
+        let mut projects = crate::tasks::project::load();
+        let metadata = projects.entry(name.to_string()).or_default();
+        metadata.status = crate::tasks::project::Status::Done;
+
+        match crate::tasks::project::save(&projects) {
+            Ok(_) => log::info!("Project {name} marked as done"),
+            Err(err) => log::error!("{err}"),
+        }
+
+        sender.output(MsgOutput::Updated).ok();
+    }
+
+    /// Marks every active project as reviewed today.
+    fn finish(&mut self, sender: &relm4::ComponentSender<Self>) {
+        use crate::tasks::project::Status;
+
+        let today = crate::date::today();
+        let list = crate::application::tasks();
+        let mut projects = crate::tasks::project::load();
+
+        let names = list
+            .tasks
+            .iter()
+            .filter(|x| !x.finished)
+            .flat_map(|x| x.projects.iter())
+            .cloned()
+            .collect::<std::collections::BTreeSet<_>>();
+
+        for name in names {
+            projects.entry(name).or_default();
+        }
+
+        for metadata in projects.values_mut() {
+            if metadata.status == Status::Active {
+                metadata.reviewed = Some(today);
+            }
+        }
+
+        match crate::tasks::project::save(&projects) {
+            Ok(_) => log::info!("Weekly review done"),
+            Err(err) => log::error!("{err}"),
+        }
+
+        self.step = crate::tasks::review::Step::Inbox;
+        sender.output(MsgOutput::Updated).ok();
+    }
+}
+
+#[relm4::component(pub)]
+impl relm4::Component for Model {
+    type CommandOutput = ();
+    type Init = ();
+    type Input = MsgInput;
+    type Output = MsgOutput;
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        use relm4::ComponentController as _;
+
+        let tasks = crate::widgets::tasks::Model::builder()
+            .launch(().into())
+            .forward(sender.output_sender(), MsgOutput::Task);
+
+        let model = Self {
+            step: crate::tasks::review::Step::Inbox,
+            tasks,
+        };
+
+        let widgets = view_output!();
+
+        model.update_step(&widgets, &sender);
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        match msg {
+            MsgInput::Finish => self.finish(&sender),
+            MsgInput::Next => {
+                if let Some(step) = self.step.next() {
+                    self.step = step;
+                }
+            }
+            MsgInput::NextAction(name, text) => self.next_action(&sender, &name, &text),
+            MsgInput::Previous => {
+                if let Some(step) = self.step.previous() {
+                    self.step = step;
+                }
+            }
+            MsgInput::ProjectDone(name) => self.project_done(&sender, &name),
+            MsgInput::Update => (),
+        }
+
+        self.update_step(widgets, &sender);
+    }
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 5,
+
+            gtk::Box {
+                set_margin_end: 10,
+                set_margin_start: 10,
+                set_margin_top: 10,
+                set_orientation: gtk::Orientation::Horizontal,
+                set_spacing: 5,
+
+                gtk::Box {
+                    set_hexpand: true,
+                    set_orientation: gtk::Orientation::Vertical,
+
+                    #[name = "title"]
+                    gtk::Label {
+                        add_css_class: "title-4",
+                        set_xalign: 0.,
+                    },
+                    #[name = "description"]
+                    gtk::Label {
+                        add_css_class: "dim-label",
+                        set_wrap: true,
+                        set_xalign: 0.,
+                    },
+                },
+                #[name = "previous"]
+                gtk::Button {
+                    set_label: "Previous",
+                    set_valign: gtk::Align::Center,
+                    connect_clicked => MsgInput::Previous,
+                },
+                #[name = "next"]
+                gtk::Button {
+                    add_css_class: "suggested-action",
+                    set_label: "Next",
+                    set_valign: gtk::Align::Center,
+                    connect_clicked => MsgInput::Next,
+                },
+                #[name = "finish"]
+                gtk::Button {
+                    add_css_class: "suggested-action",
+                    set_label: "Finish",
+                    set_tooltip_text: Some("Mark active projects as reviewed today"),
+                    set_valign: gtk::Align::Center,
+                    connect_clicked => MsgInput::Finish,
+                },
+            },
+            #[name = "projects_window"]
+            gtk::ScrolledWindow {
+                set_vexpand: true,
+
+                #[name = "projects"]
+                gtk::ListBox {
+                    set_selection_mode: gtk::SelectionMode::None,
+                },
+            },
+            append: model.tasks.widget(),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use adw::prelude::*;
 use relm4::ComponentController as _;
 
+const STALE_FILTER: &str = "is:stale";
+
 static CURRENT_FILTER: std::sync::LazyLock<std::sync::RwLock<String>> =
     std::sync::LazyLock::new(|| std::sync::RwLock::new(String::new()));
 
@@ -15,18 +17,34 @@ pub struct Model {
 }
 
 impl Model {
-    fn tasks() -> Vec<crate::tasks::Task> {
+    pub fn tasks() -> Vec<crate::tasks::Task> {
         let current_filter = CURRENT_FILTER.read().unwrap();
 
-        let filter = current_filter.to_lowercase();
+        let (stale, filter) = Self::parse(&current_filter);
         let list = crate::application::tasks();
+        let today = crate::date::today();
 
         list.tasks
             .iter()
-            .filter(|x| x.subject.to_lowercase().contains(filter.as_str()))
+            .filter(|x| {
+                (!stale || crate::tasks::review::is_stale(x, today))
+                    && (x.subject.to_lowercase().contains(filter.as_str())
+                        || crate::tasks::index::note_matches(x, &filter))
+            })
             .cloned()
             .collect()
     }
+
+    /// Lowercased text to search, without `is:stale` which only keeps tasks
+    /// untouched for a long time.
+    fn parse(filter: &str) -> (bool, String) {
+        let filter = filter.to_lowercase();
+
+        match filter.split_once(STALE_FILTER) {
+            Some((before, after)) => (true, format!("{before}{after}").trim().to_string()),
+            None => (false, filter),
+        }
+    }
 }
 
 #[relm4::component(pub)]
@@ -59,6 +77,10 @@ impl relm4::SimpleComponent for Model {
         match msg {
             Update => self.tasks.emit(crate::widgets::tasks::MsgInput::NeedUpdate),
             UpdateFilter(filter) => {
+                let (_, query) = Self::parse(&filter);
+                self.tasks
+                    .emit(crate::widgets::tasks::MsgInput::Highlight(query));
+
                 let mut current_filter = CURRENT_FILTER.write().unwrap();
                 *current_filter = filter;
                 sender.input(Update);
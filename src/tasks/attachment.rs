@@ -0,0 +1,199 @@
+/// Attachments are copied in `$TODO_NOTES_DIR/attachments/<dir>/` and linked
+/// from the task note, links are relative to the notes directory.
+const DIR: &str = "attachments";
+
+pub fn notes_dir() -> std::path::PathBuf {
+    match std::env::var("TODO_NOTES_DIR") {
+        Ok(notes_dir) => notes_dir.into(),
+        Err(_) => {
+            std::path::Path::new(&std::env::var("TODO_DIR").unwrap_or_default()).join("notes")
+        }
+    }
+}
+
+/// Files attached to a task.
+pub fn list(task: &crate::tasks::Task) -> Vec<std::path::PathBuf> {
+    let notes_dir = notes_dir();
+
+    links(&task.note.content().unwrap_or_default())
+        .iter()
+        .map(|x| notes_dir.join(x))
+        .collect()
+}
+
+/// Copies `files` in the task attachment directory and links them from its note.
+pub fn attach(task: &mut crate::tasks::Task, files: &[std::path::PathBuf]) -> Result<(), String> {
+    copy(&add(task, files))
+}
+
+/// Links `files` from the task note, without copying them yet: returns each
+/// file with its path relative to the notes directory, for [`copy`].
+pub fn add(
+    task: &mut crate::tasks::Task,
+    files: &[std::path::PathBuf],
+) -> Vec<(std::path::PathBuf, String)> {
+    let mut content = task.note.content().unwrap_or_default();
+    let mut added = Vec::new();
+
+    let dir = links(&content)
+        .first()
+        .and_then(|x| x.rsplit_once('/'))
+        .map(|(dir, _)| dir.to_string())
+        .unwrap_or_else(|| format!("{DIR}/{}", chrono::Local::now().format("%Y%m%d%H%M%S%3f")));
+
+    for file in files {
+        let Some(name) = file.file_name().and_then(|x| x.to_str()) else {
+            continue;
+        };
+        let name = unique_name(&dir, name, &links(&content));
+        let path = format!("{dir}/{name}");
+
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&link(&path, &name));
+        added.push((file.clone(), path));
+    }
+
+    task.note = match &task.note {
+        todo_txt::task::Note::Long { filename, .. } => todo_txt::task::Note::Long {
+            filename: filename.clone(),
+            content,
+        },
+        _ => content.into(),
+    };
+
+    added
+}
+
+/// Copies the files returned by [`add`] in the notes directory.
+pub fn copy(files: &[(std::path::PathBuf, String)]) -> Result<(), String> {
+    let notes_dir = notes_dir();
+
+    for (file, path) in files {
+        let target = notes_dir.join(path);
+
+        if let Some(target_dir) = target.parent() {
+            std::fs::create_dir_all(target_dir)
+                .map_err(|err| format!("Unable to create {}: {err}", target_dir.display()))?;
+        }
+
+        std::fs::copy(file, &target)
+            .map_err(|err| format!("Unable to attach {}: {err}", file.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Files still linked from the task note.
+pub fn linked(
+    task: &crate::tasks::Task,
+    files: &[(std::path::PathBuf, String)],
+) -> Vec<(std::path::PathBuf, String)> {
+    let links = links(&task.note.content().unwrap_or_default());
+
+    files
+        .iter()
+        .filter(|(_, path)| links.contains(path))
+        .cloned()
+        .collect()
+}
+
+/// A name not used in `dir`, neither on disk nor by the `links` not copied yet.
+fn unique_name(dir: &str, name: &str, links: &[String]) -> String {
+    let path = std::path::Path::new(name);
+    let stem = path.file_stem().and_then(|x| x.to_str()).unwrap_or(name);
+    let extension = path
+        .extension()
+        .and_then(|x| x.to_str())
+        .map(|x| format!(".{x}"))
+        .unwrap_or_default();
+
+    let mut candidate = name.to_string();
+    let mut n = 1;
+
+    let taken = |candidate: &str| {
+        let path = format!("{dir}/{candidate}");
+
+        notes_dir().join(&path).exists() || links.contains(&path)
+    };
+
+    while taken(&candidate) {
+        candidate = format!("{stem}-{n}{extension}");
+        n += 1;
+    }
+
+    candidate
+}
+
+fn link(path: &str, name: &str) -> String {
+    format!("- [{}](<{path}>)\n", name.replace(['[', ']'], ""))
+}
+
+/// Attachment paths linked from a note, links leaving the attachments
+/// directory are ignored.
+fn links(note: &str) -> Vec<String> {
+    use pulldown_cmark::{Event, Tag};
+
+    pulldown_cmark::Parser::new_ext(note, super::markup::options())
+        .filter_map(|event| match event {
+            Event::Start(Tag::Link { dest_url, .. }) if is_attachment(&dest_url) => {
+                Some(dest_url.to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_attachment(path: &str) -> bool {
+    path.starts_with(&format!("{DIR}/"))
+        && std::path::Path::new(path)
+            .components()
+            .all(|x| matches!(x, std::path::Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn links() {
+        let note = format!(
+            "Some text [site](https://example.org)\n{}",
+            super::link("attachments/42/my file.pdf", "my [file].pdf")
+        );
+
+        assert_eq!(
+            note,
+            "Some text [site](https://example.org)\n- [my file.pdf](<attachments/42/my file.pdf>)\n"
+        );
+        assert_eq!(
+            super::links(&note),
+            vec!["attachments/42/my file.pdf".to_string()]
+        );
+    }
+
+    #[test]
+    fn outside_links() {
+        let note = "[a](attachmentsX/a.pdf) [b](attachments/../../b.pdf) [c](/attachments/c.pdf) [d](attachments/1/d.pdf)";
+
+        assert_eq!(super::links(note), vec!["attachments/1/d.pdf".to_string()]);
+    }
+
+    #[test]
+    fn add() {
+        let mut task = crate::tasks::Task::from("Read".to_string());
+        task.note = "- [a.pdf](<attachments/1/a.pdf>)".to_string().into();
+
+        let added = super::add(&mut task, &["/tmp/a.pdf".into(), "/tmp/b.txt".into()]);
+
+        assert_eq!(
+            added,
+            vec![
+                ("/tmp/a.pdf".into(), "attachments/1/a-1.pdf".to_string()),
+                ("/tmp/b.txt".into(), "attachments/1/b.txt".to_string()),
+            ]
+        );
+
+        task.note = "- [b.txt](<attachments/1/b.txt>)".to_string().into();
+        assert_eq!(super::linked(&task, &added), added[1..]);
+    }
+}
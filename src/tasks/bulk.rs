@@ -0,0 +1,195 @@
+/// An action applied at once on several selected tasks.
+#[derive(Clone, Debug)]
+pub enum Action {
+    AddContext(String),
+    AddHashtag(String),
+    AddProject(String),
+    Complete,
+    Defer(Option<chrono::NaiveDate>),
+    /// `notes` also removes the note files, which is left to the caller.
+    Delete {
+        notes: bool,
+    },
+    Due(Option<chrono::NaiveDate>),
+    Flag(bool),
+    /// Replaces all the task projects.
+    Move(String),
+    /// Replaces all the task contexts.
+    MoveContext(String),
+    /// Replaces all the task hashtags.
+    MoveHashtag(String),
+    /// Plans the tasks for a day, in the `ids` order, after the tasks already
+    /// planned that day.
+    Plan(Option<chrono::NaiveDate>),
+    Priority(todo_txt::Priority),
+    RemoveContext(String),
+    RemoveProject(String),
+}
+
+/// Applies `action` on the tasks whose id is in `ids`.
+pub fn apply(tasks: &mut Vec<crate::tasks::Task>, ids: &[usize], action: &Action) {
+    use Action::*;
+
+    if matches!(action, Delete { .. }) {
+        tasks.retain(|x| !ids.contains(&x.id));
+        return;
+    }
+
+    if let Plan(date) = action {
+        plan(tasks, ids, *date);
+        return;
+    }
+
+    let mut next = Vec::new();
+
+    for task in tasks.iter_mut().filter(|x| ids.contains(&x.id)) {
+        match action {
+            AddContext(context) => add_tag(task, '@', context),
+            AddHashtag(hashtag) => add_tag(task, '#', hashtag),
+            AddProject(project) => add_tag(task, '+', project),
+            Complete => {
+                if !task.finished {
+                    task.complete();
+                    next.extend(task.next_occurrence());
+                }
+            }
+            Defer(date) => task.threshold_date = *date,
+            Delete { .. } => (),
+            Due(date) => task.due_date = *date,
+            Flag(flagged) => task.flagged = *flagged,
+            Move(project) => replace_tags(task, '+', project),
+            MoveContext(context) => replace_tags(task, '@', context),
+            MoveHashtag(hashtag) => replace_tags(task, '#', hashtag),
+            Plan(_) => (),
+            Priority(priority) => task.priority = priority.clone(),
+            RemoveContext(context) => remove_tag(task, '@', context),
+            RemoveProject(project) => remove_tag(task, '+', project),
+        }
+    }
+
+    tasks.extend(next);
+}
+
+fn plan(tasks: &mut [crate::tasks::Task], ids: &[usize], date: Option<chrono::NaiveDate>) {
+    let last = tasks
+        .iter()
+        .filter(|x| !ids.contains(&x.id))
+        .filter_map(|x| x.plan())
+        .filter(|(planned, _)| Some(*planned) == date)
+        .map(|(_, rank)| rank)
+        .max()
+        .unwrap_or_default();
+
+    for (n, id) in ids.iter().enumerate() {
+        if let Some(task) = tasks.iter_mut().find(|x| x.id == *id) {
+            task.set_plan(date.map(|date| (date, last + n + 1)));
+        }
+    }
+}
+
+fn add_tag(task: &mut crate::tasks::Task, prefix: char, name: &str) {
+    let Some(name) = tag_name(prefix, name) else {
+        return;
+    };
+
+    let tags = tags(task, prefix);
+
+    if !tags.contains(&name) {
+        tags.push(name.clone());
+        task.subject.push_str(&format!(" {prefix}{name}"));
+    }
+}
+
+fn remove_tag(task: &mut crate::tasks::Task, prefix: char, name: &str) {
+    let Some(name) = tag_name(prefix, name) else {
+        return;
+    };
+
+    tags(task, prefix).retain(|x| *x != name);
+
+    let token = format!("{prefix}{name}");
+    task.subject = task
+        .subject
+        .split_whitespace()
+        .filter(|x| *x != token)
+        .collect::<Vec<_>>()
+        .join(" ");
+}
+
+fn replace_tags(task: &mut crate::tasks::Task, prefix: char, name: &str) {
+    for old in tags(task, prefix).clone() {
+        remove_tag(task, prefix, &old);
+    }
+    add_tag(task, prefix, name);
+}
+
+fn tags(task: &mut crate::tasks::Task, prefix: char) -> &mut Vec<String> {
+    match prefix {
+        '+' => &mut task.projects,
+        '@' => &mut task.contexts,
+        _ => &mut task.hashtags,
+    }
+}
+
+/// A tag name typed by the user, without its prefix.
+fn tag_name(prefix: char, name: &str) -> Option<String> {
+    let name = name.trim().trim_start_matches(prefix);
+
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn apply() {
+        use super::Action::*;
+
+        let mut tasks = ["Call +work @phone", "Buy milk", "Write +work +blog"]
+            .iter()
+            .enumerate()
+            .map(|(id, x)| {
+                let mut task = crate::tasks::Task::from(x.to_string());
+                task.id = id;
+                task
+            })
+            .collect::<Vec<_>>();
+
+        super::apply(&mut tasks, &[0, 1], &AddContext("@home".to_string()));
+        super::apply(&mut tasks, &[0, 2], &RemoveProject("work".to_string()));
+        super::apply(&mut tasks, &[2], &Move("+perso".to_string()));
+        super::apply(&mut tasks, &[1], &AddProject("two words".to_string()));
+        super::apply(&mut tasks, &[0], &Priority(todo_txt::Priority::from(0)));
+
+        assert_eq!(tasks[0].subject, "Call @phone @home");
+        assert_eq!(tasks[0].contexts, vec!["phone", "home"]);
+        assert_eq!(tasks[0].priority, 'A');
+        assert_eq!(tasks[1].subject, "Buy milk @home");
+        assert_eq!(tasks[2].subject, "Write +perso");
+        assert_eq!(tasks[2].projects, vec!["perso"]);
+
+        super::apply(&mut tasks, &[0], &MoveContext("office".to_string()));
+        super::apply(&mut tasks, &[0], &AddHashtag("#call".to_string()));
+        assert_eq!(tasks[0].subject, "Call @office #call");
+
+        let today = chrono::NaiveDate::from_ymd_opt(2042, 1, 1).unwrap();
+        super::apply(&mut tasks, &[2], &Plan(Some(today)));
+        super::apply(&mut tasks, &[1, 0], &Plan(Some(today)));
+        assert_eq!(tasks[0].plan(), Some((today, 3)));
+        assert_eq!(tasks[1].plan(), Some((today, 2)));
+        assert_eq!(tasks[2].to_string(), "Write +perso plan:2042-01-01/1");
+
+        super::apply(&mut tasks, &[2], &Plan(None));
+        assert_eq!(tasks[2].plan(), None);
+
+        super::apply(&mut tasks, &[1, 2], &Complete);
+        assert!(tasks[1].finished && tasks[2].finished && !tasks[0].finished);
+
+        super::apply(&mut tasks, &[0, 2], &Delete { notes: false });
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, 1);
+    }
+}
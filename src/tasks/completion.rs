@@ -0,0 +1,117 @@
+/// The `+project`, `@context` and `#hashtag` tokens of the list.
+pub fn tags(list: &crate::tasks::List) -> Vec<String> {
+    let projects = list.projects().into_iter().map(|x| format!("+{x}"));
+    let contexts = list.contexts().into_iter().map(|x| format!("@{x}"));
+    let hashtags = list.hashtags().into_iter().map(|x| format!("#{x}"));
+
+    projects.chain(contexts).chain(hashtags).collect()
+}
+
+/// The `key:value` tag names used by the tasks, apart from the ones with a
+/// dedicated field.
+pub fn keys(list: &crate::tasks::List) -> Vec<String> {
+    list.tasks
+        .iter()
+        .flat_map(|x| x.tags.keys())
+        .filter(|x| !crate::tasks::Task::SPECIAL_TAGS.contains(&x.as_str()))
+        .cloned()
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// The word being typed before `cursor` (a char offset), with its byte range.
+fn current_word(text: &str, cursor: usize) -> (usize, usize) {
+    let end = text
+        .char_indices()
+        .nth(cursor)
+        .map_or(text.len(), |(n, _)| n);
+    let start = text[..end].rfind(char::is_whitespace).map_or(0, |n| {
+        n + text[n..].chars().next().map_or(1, char::len_utf8)
+    });
+
+    (start, end)
+}
+
+/// Candidates completing the word before the cursor, when it starts with one
+/// of `triggers` (or any word when there is no trigger).
+pub fn suggestions(
+    text: &str,
+    cursor: usize,
+    triggers: &str,
+    candidates: &[String],
+) -> Vec<String> {
+    const MAX: usize = 8;
+
+    let (start, end) = current_word(text, cursor);
+    let word = &text[start..end];
+
+    if word.is_empty() || !(triggers.is_empty() || word.starts_with(|c| triggers.contains(c))) {
+        return Vec::new();
+    }
+
+    let word = word.to_lowercase();
+
+    candidates
+        .iter()
+        .filter(|x| x.to_lowercase().starts_with(&word) && x.len() > word.len())
+        .take(MAX)
+        .cloned()
+        .collect()
+}
+
+/// Replaces the word before the cursor by `suggestion`, followed by a space
+/// when `separate`, returns the new text and cursor position.
+pub fn complete(text: &str, cursor: usize, suggestion: &str, separate: bool) -> (String, usize) {
+    let (start, end) = current_word(text, cursor);
+
+    let mut completed = format!("{}{suggestion}", &text[..start]);
+    let rest = &text[end..];
+
+    if separate && !rest.starts_with(char::is_whitespace) {
+        completed.push(' ');
+    }
+
+    let cursor = completed.chars().count();
+    completed.push_str(rest);
+
+    (completed, cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn complete() {
+        let candidates = ["+work", "+work\\admin", "+perso", "@phone"]
+            .map(String::from)
+            .to_vec();
+
+        assert_eq!(
+            super::suggestions("Call +Wo", 8, "+@#", &candidates),
+            vec!["+work", "+work\\admin"]
+        );
+        assert_eq!(
+            super::suggestions("Call +wo later", 8, "+@#", &candidates),
+            vec!["+work", "+work\\admin"]
+        );
+        assert!(super::suggestions("Call wo", 7, "+@#", &candidates).is_empty());
+        assert!(super::suggestions("Call +work", 10, "+@#", &candidates).len() == 1);
+
+        assert_eq!(
+            super::complete("Été @ph", 7, "@phone", true),
+            ("Été @phone ".to_string(), 11)
+        );
+        assert_eq!(
+            super::complete("Call +wo later", 8, "+work", true),
+            ("Call +work later".to_string(), 10)
+        );
+        assert_eq!(
+            super::complete("du", 2, "duration", false),
+            ("duration".to_string(), 8)
+        );
+        assert_eq!(
+            super::suggestions("est", 3, "", &["estimate".to_string()]),
+            vec!["estimate"]
+        );
+    }
+}
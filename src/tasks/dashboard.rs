@@ -0,0 +1,287 @@
+use chrono::Datelike as _;
+
+/// Number of days and weeks shown.
+pub const DAYS: usize = 14;
+pub const WEEKS: usize = 8;
+/// Entries kept by distribution.
+const TOP: usize = 8;
+
+/// Tasks created and completed during a period, starting at `date`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Period {
+    pub date: chrono::NaiveDate,
+    pub created: usize,
+    pub completed: usize,
+}
+
+/// Consecutive occurrences of a recurring task completed on time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Streak {
+    pub subject: String,
+    pub current: usize,
+    pub best: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Dashboard {
+    pub days: Vec<Period>,
+    pub weeks: Vec<Period>,
+    /// Overdue tasks at the end of each of the last days.
+    pub overdue: Vec<(chrono::NaiveDate, usize)>,
+    /// Open tasks by priority, context and project, the largest first.
+    pub priorities: Vec<(String, usize)>,
+    pub contexts: Vec<(String, usize)>,
+    pub projects: Vec<(String, usize)>,
+    pub streaks: Vec<Streak>,
+}
+
+impl Dashboard {
+    pub fn new(tasks: &[crate::tasks::Task], today: chrono::NaiveDate) -> Self {
+        let open = tasks.iter().filter(|x| !x.finished).collect::<Vec<_>>();
+
+        let priority = |task: &&crate::tasks::Task| {
+            if task.priority.is_lowest() {
+                vec!["none".to_string()]
+            } else {
+                vec![task.priority.to_string()]
+            }
+        };
+
+        Self {
+            days: periods(
+                tasks,
+                today - chrono::Duration::days(DAYS as i64 - 1),
+                1,
+                DAYS,
+            ),
+            weeks: periods(
+                tasks,
+                week(today) - chrono::Duration::weeks(WEEKS as i64 - 1),
+                7,
+                WEEKS,
+            ),
+            overdue: overdue(tasks, today),
+            priorities: distribution(&open, priority),
+            contexts: distribution(&open, |x| x.contexts.clone()),
+            projects: distribution(&open, |x| x.projects.clone()),
+            streaks: streaks(tasks),
+        }
+    }
+}
+
+/// The monday of the `date` week.
+fn week(date: chrono::NaiveDate) -> chrono::NaiveDate {
+    date - chrono::Duration::days(date.weekday().num_days_from_monday().into())
+}
+
+fn periods(
+    tasks: &[crate::tasks::Task],
+    start: chrono::NaiveDate,
+    days: i64,
+    count: usize,
+) -> Vec<Period> {
+    (0..count)
+        .map(|n| {
+            let date = start + chrono::Duration::days(n as i64 * days);
+            let end = date + chrono::Duration::days(days);
+            let within = |x: Option<chrono::NaiveDate>| x.is_some_and(|x| x >= date && x < end);
+
+            Period {
+                date,
+                created: tasks.iter().filter(|x| within(x.create_date)).count(),
+                completed: tasks.iter().filter(|x| within(x.finish_date)).count(),
+            }
+        })
+        .collect()
+}
+
+/// Tasks due before a day and still open at its end.
+fn overdue(
+    tasks: &[crate::tasks::Task],
+    today: chrono::NaiveDate,
+) -> Vec<(chrono::NaiveDate, usize)> {
+    (0..DAYS)
+        .rev()
+        .map(|n| {
+            let date = today - chrono::Duration::days(n as i64);
+            let count = tasks
+                .iter()
+                .filter(|x| {
+                    x.due_date.is_some_and(|due| due < date)
+                        && x.create_date.is_none_or(|created| created <= date)
+                        && !(x.finished && x.finish_date.is_none_or(|finished| finished <= date))
+                })
+                .count();
+
+            (date, count)
+        })
+        .collect()
+}
+
+fn distribution<F: Fn(&&crate::tasks::Task) -> Vec<String>>(
+    tasks: &[&crate::tasks::Task],
+    keys: F,
+) -> Vec<(String, usize)> {
+    let mut counts = std::collections::BTreeMap::<String, usize>::new();
+
+    for key in tasks.iter().flat_map(keys) {
+        *counts.entry(key).or_default() += 1;
+    }
+
+    let mut distribution = counts.into_iter().collect::<Vec<_>>();
+    distribution.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    distribution.truncate(TOP);
+
+    distribution
+}
+
+/// Completed occurrences of recurring tasks are grouped by subject, an
+/// occurrence completed after its due date breaks the streak.
+fn streaks(tasks: &[crate::tasks::Task]) -> Vec<Streak> {
+    let mut occurrences = std::collections::BTreeMap::<&str, Vec<_>>::new();
+
+    for task in tasks
+        .iter()
+        .filter(|x| x.recurrence.is_some() && x.finished)
+    {
+        occurrences
+            .entry(task.subject.as_str())
+            .or_default()
+            .push(task);
+    }
+
+    let mut streaks = occurrences
+        .into_iter()
+        .map(|(subject, mut occurrences)| {
+            occurrences.sort_by_key(|x| x.due_date.or(x.finish_date));
+
+            let mut current = 0;
+            let mut best = 0;
+
+            for task in occurrences {
+                let on_time = match (task.finish_date, task.due_date) {
+                    (Some(finished), Some(due)) => finished <= due,
+                    _ => true,
+                };
+
+                current = if on_time { current + 1 } else { 0 };
+                best = best.max(current);
+            }
+
+            Streak {
+                subject: subject.to_string(),
+                current,
+                best,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    streaks.sort_by(|a, b| {
+        b.current
+            .cmp(&a.current)
+            .then_with(|| a.subject.cmp(&b.subject))
+    });
+
+    streaks
+}
+
+#[cfg(test)]
+mod tests {
+    fn new(lines: &[&str]) -> super::Dashboard {
+        let tasks = lines
+            .iter()
+            .map(|x| crate::tasks::Task::from(x.to_string()))
+            .collect::<Vec<_>>();
+
+        super::Dashboard::new(&tasks, date(15))
+    }
+
+    fn date(day: u32) -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(2042, 1, day).unwrap()
+    }
+
+    const TASKS: [&str; 6] = [
+        "x 2042-01-15 2042-01-14 Write +book @desk",
+        "(A) 2042-01-14 Review +book @desk due:2042-01-13",
+        "2042-01-01 Call @phone",
+        "x 2042-01-08 2042-01-01 Run due:2042-01-08 rec:1w",
+        "x 2042-01-16 2042-01-08 Run due:2042-01-15 rec:1w",
+        "x 2042-01-15 2042-01-15 Run due:2042-01-22 rec:1w",
+    ];
+
+    #[test]
+    fn periods() {
+        let dashboard = new(&TASKS);
+        let last = dashboard.days.last().unwrap();
+        let week = dashboard.weeks.last().unwrap();
+
+        assert_eq!(dashboard.days.len(), super::DAYS);
+        assert_eq!((last.date, last.created, last.completed), (date(15), 1, 2));
+        assert_eq!(dashboard.weeks.len(), super::WEEKS);
+        assert_eq!((week.date, week.created, week.completed), (date(13), 3, 3));
+
+        let empty = new(&["Write", "x Print"]);
+        assert!(
+            empty
+                .days
+                .iter()
+                .chain(&empty.weeks)
+                .all(|x| x.created == 0 && x.completed == 0)
+        );
+
+        // Finished before created: each date is counted on its own day
+        let dashboard = new(&["x 2042-01-13 2042-01-14 Print"]);
+        let day = |date| dashboard.days.iter().find(|x| x.date == date).unwrap();
+        assert_eq!(day(date(13)).completed, 1);
+        assert_eq!(day(date(14)).created, 1);
+    }
+
+    #[test]
+    fn overdue() {
+        let overdue = new(&TASKS).overdue;
+
+        assert_eq!(overdue.last(), Some(&(date(15), 1)));
+        assert_eq!(overdue[super::DAYS - 3], (date(13), 0));
+
+        let overdue = new(&["x 2042-01-13 2042-01-14 Print due:2042-01-12"]).overdue;
+        assert!(overdue.iter().all(|(_, count)| *count == 0));
+    }
+
+    #[test]
+    fn distribution() {
+        let dashboard = new(&TASKS);
+
+        assert_eq!(
+            dashboard.priorities,
+            vec![("A".to_string(), 1), ("none".to_string(), 1)]
+        );
+        assert_eq!(
+            dashboard.contexts,
+            vec![("desk".to_string(), 1), ("phone".to_string(), 1)]
+        );
+        assert_eq!(dashboard.projects, vec![("book".to_string(), 1)]);
+        assert!(super::Dashboard::new(&[], date(15)).priorities.is_empty());
+    }
+
+    #[test]
+    fn streaks() {
+        let streak = |subject: &str, current, best| super::Streak {
+            subject: subject.to_string(),
+            current,
+            best,
+        };
+
+        assert_eq!(new(&TASKS).streaks, vec![streak("Run", 1, 1)]);
+        assert_eq!(
+            new(&[
+                "x 2042-01-01 2042-01-01 Swim due:2042-01-01 rec:1w",
+                "x 2042-01-08 2042-01-01 Swim due:2042-01-08 rec:1w",
+                "x 2042-01-16 2042-01-01 Swim due:2042-01-15 rec:1w",
+            ])
+            .streaks,
+            vec![streak("Swim", 0, 2)]
+        );
+        assert_eq!(new(&["x Run rec:1w"]).streaks, vec![streak("Run", 1, 1)]);
+        assert!(super::Dashboard::new(&[], date(15)).streaks.is_empty());
+    }
+}
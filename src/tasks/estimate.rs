@@ -0,0 +1,123 @@
+/// Estimated work of the unfinished tasks.
+pub fn total(tasks: &[crate::tasks::Task]) -> chrono::Duration {
+    tasks
+        .iter()
+        .filter(|x| !x.finished)
+        .filter_map(|x| x.estimate())
+        .sum()
+}
+
+/// Days whose estimated work exceeds `capacity`, with their workload.
+pub fn overloaded(
+    tasks: &[crate::tasks::Task],
+    capacity: chrono::Duration,
+) -> Vec<(chrono::NaiveDate, chrono::Duration)> {
+    let mut days = std::collections::BTreeMap::<_, chrono::Duration>::new();
+
+    for task in tasks.iter().filter(|x| !x.finished) {
+        if let (Some(due_date), Some(estimate)) = (task.due_date, task.estimate()) {
+            *days.entry(due_date).or_default() += estimate;
+        }
+    }
+
+    days.into_iter()
+        .filter(|(_, workload)| *workload > capacity)
+        .collect()
+}
+
+/// Estimated work and overloaded days, `None` without any estimate.
+pub fn summary(tasks: &[crate::tasks::Task], capacity: Option<chrono::Duration>) -> Option<String> {
+    let total = total(tasks);
+
+    if total.is_zero() {
+        return None;
+    }
+
+    let mut summary = format!("{} estimated", crate::date::format_duration(total));
+
+    if let Some(capacity) = capacity {
+        let days = overloaded(tasks, capacity)
+            .iter()
+            .map(|(date, workload)| {
+                format!(
+                    "{} on {}",
+                    crate::date::format_duration(*workload),
+                    date.format("%Y-%m-%d")
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if !days.is_empty() {
+            summary.push_str(&format!(
+                " ⚠ over the {} capacity: {}",
+                crate::date::format_duration(capacity),
+                days.join(", ")
+            ));
+        }
+    }
+
+    Some(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    fn tasks(lines: &[&str]) -> Vec<crate::tasks::Task> {
+        lines
+            .iter()
+            .map(|x| crate::tasks::Task::from(x.to_string()))
+            .collect()
+    }
+
+    fn week() -> Vec<crate::tasks::Task> {
+        tasks(&[
+            "Write due:2042-01-15 est:3h",
+            "Review due:2042-01-15 est:2h30m",
+            "x Print due:2042-01-15 est:4h",
+            "Call due:2042-01-16 est:30m",
+            "Read due:2042-01-16",
+        ])
+    }
+
+    #[test]
+    fn total() {
+        assert_eq!(super::total(&week()), chrono::Duration::minutes(360));
+        assert!(super::total(&[]).is_zero());
+    }
+
+    #[test]
+    fn overloaded() {
+        let date = chrono::NaiveDate::from_ymd_opt(2042, 1, 15).unwrap();
+
+        assert_eq!(
+            super::overloaded(&week(), chrono::Duration::hours(5)),
+            [(date, chrono::Duration::minutes(330))]
+        );
+        assert!(super::overloaded(&week(), chrono::Duration::hours(8)).is_empty());
+        assert!(
+            super::overloaded(&tasks(&["Write est:10h"]), chrono::Duration::hours(8)).is_empty()
+        );
+    }
+
+    #[test]
+    fn summary() {
+        let summary = |tasks: &[crate::tasks::Task], hours: Option<i64>| {
+            super::summary(tasks, hours.map(chrono::Duration::hours))
+        };
+
+        assert_eq!(
+            summary(&week(), Some(5)).as_deref(),
+            Some("6h estimated ⚠ over the 5h capacity: 5h30m on 2042-01-15")
+        );
+        assert_eq!(summary(&week(), Some(8)).as_deref(), Some("6h estimated"));
+        assert_eq!(summary(&week(), None).as_deref(), Some("6h estimated"));
+        assert_eq!(
+            summary(&tasks(&["Write est:10h"]), Some(8)).as_deref(),
+            Some("10h estimated")
+        );
+        assert_eq!(summary(&[], Some(8)), None);
+        assert_eq!(
+            summary(&tasks(&["Read due:2042-01-16", "x Print est:4h"]), None),
+            None
+        );
+    }
+}
@@ -0,0 +1,135 @@
+/// Lowercased contents of the note files, by file name, so they aren't
+/// lowercased again on each keystroke of a search.
+static INDEX: std::sync::LazyLock<std::sync::Mutex<std::collections::HashMap<String, String>>> =
+    std::sync::LazyLock::new(Default::default);
+
+/// `query` must already be lowercase.
+pub fn note_matches(task: &crate::tasks::Task, query: &str) -> bool {
+    let todo_txt::task::Note::Long { filename, content } = &task.note else {
+        return task
+            .note
+            .content()
+            .is_some_and(|x| x.to_lowercase().contains(query));
+    };
+
+    INDEX
+        .lock()
+        .unwrap()
+        .entry(filename.clone())
+        .or_insert_with(|| content.to_lowercase())
+        .contains(query)
+}
+
+/// Forgets the note saved in `filename`.
+pub fn invalidate(filename: &str) {
+    INDEX.lock().unwrap().remove(filename);
+}
+
+/// Forgets all notes, when the list is reloaded.
+pub fn clear() {
+    INDEX.lock().unwrap().clear();
+}
+
+/// Pango markup of the note text around the first match of `query`.
+pub fn snippet(task: &crate::tasks::Task, query: &str) -> Option<String> {
+    const BEFORE: usize = 30;
+    const AFTER: usize = 60;
+
+    let query = query.to_lowercase();
+
+    if query.is_empty() {
+        return None;
+    }
+
+    let content = task.note.content()?;
+    let chars = content.chars().collect::<Vec<_>>();
+    let (lowercase, origins) = lowercase(&chars);
+
+    let found = lowercase[..lowercase.find(&query)?].chars().count();
+    let start = origins[found];
+    let end = origins[found + query.chars().count() - 1] + 1;
+
+    let text = |from: usize, to: usize| {
+        super::markup::escape(&chars[from..to].iter().collect::<String>()).replace('\n', " ")
+    };
+
+    let from = start.saturating_sub(BEFORE);
+    let to = (end + AFTER).min(chars.len());
+
+    Some(format!(
+        "{}{}<b>{}</b>{}{}",
+        if from > 0 { "…" } else { "" },
+        text(from, start),
+        text(start, end),
+        text(end, to),
+        if to < chars.len() { "…" } else { "" },
+    ))
+}
+
+/// Lowercased text, with the index of the original char of each lowercase
+/// char: some chars, like 'İ', have a longer lowercase form.
+fn lowercase(chars: &[char]) -> (String, Vec<usize>) {
+    let mut lowercase = String::new();
+    let mut origins = Vec::new();
+
+    for (n, c) in chars.iter().enumerate() {
+        for c in c.to_lowercase() {
+            lowercase.push(c);
+            origins.push(n);
+        }
+    }
+
+    (lowercase, origins)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn snippet() {
+        let mut task = crate::tasks::Task::new();
+        task.note = format!("İntro\n{} the <Key> word{}", "a".repeat(40), "b".repeat(70)).into();
+
+        assert!(super::note_matches(&task, "<key>"));
+        assert!(super::note_matches(&task, &"İntro".to_lowercase()));
+        assert!(!super::note_matches(&task, "missing"));
+        assert_eq!(
+            super::snippet(&task, "İntro"),
+            Some(format!(
+                "<b>İntro</b> {} the &lt;Key&gt; word{}…",
+                "a".repeat(40),
+                "b".repeat(4)
+            ))
+        );
+        assert_eq!(
+            super::snippet(&task, "<KEY>"),
+            Some(format!(
+                "…{} the <b>&lt;Key&gt;</b> word{}…",
+                "a".repeat(25),
+                "b".repeat(55)
+            ))
+        );
+    }
+
+    #[test]
+    fn note_matches() {
+        let mut task = crate::tasks::Task::new();
+
+        let note = |content: &str| todo_txt::task::Note::Long {
+            filename: "index-test.txt".to_string(),
+            content: content.to_string(),
+        };
+
+        task.note = note("First Draft");
+        assert!(super::note_matches(&task, "draft"));
+
+        task.note = note("Final version");
+        assert!(super::note_matches(&task, "draft"));
+        super::invalidate("index-test.txt");
+        assert!(!super::note_matches(&task, "draft"));
+        assert!(super::note_matches(&task, "final"));
+
+        task.note = note("Archived");
+        super::clear();
+        assert!(super::note_matches(&task, "archived"));
+    }
+}
@@ -37,6 +37,8 @@ impl List {
         list.todo = todo.to_string();
         list.done = done.to_string();
 
+        crate::tasks::index::clear();
+
         async_std::task::block_on(async {
             let todo = list.load_file(0, todo).await;
             list.inner.extend(todo);
@@ -91,13 +93,15 @@ impl List {
     }
 
     pub fn write(&self) -> Result<(), String> {
+        let feed = crate::export::ics::feed();
+        let mut tasks = self.inner.tasks.clone();
+
+        if feed.is_some() {
+            crate::export::ics::assign_uids(&mut tasks);
+        }
+
         async_std::task::block_on(async {
-            let (done, todo) = self
-                .inner
-                .tasks
-                .clone()
-                .into_iter()
-                .partition(|x| x.finished);
+            let (done, todo) = tasks.clone().into_iter().partition(|x| x.finished);
 
             let (a, b) = async { self.write_tasks(&self.todo, todo).await }
                 .join(async { self.write_tasks(&self.done, done).await })
@@ -106,6 +110,14 @@ impl List {
             a.and(b)
         })?;
 
+        if let Some(path) = feed {
+            let calendar = crate::export::ics::calendar(&crate::export::ics::tasks(&tasks));
+
+            if let Err(err) = crate::export::write(std::path::Path::new(&path), &calendar) {
+                log::error!("{err}");
+            }
+        }
+
         Ok(())
     }
 
@@ -123,6 +135,8 @@ impl List {
             if let Err(err) = task.note.write() {
                 log::error!("Unable to save note: {err}");
                 task.note = todo_txt::task::Note::None;
+            } else if let todo_txt::task::Note::Long { filename, .. } = &task.note {
+                crate::tasks::index::invalidate(filename);
             }
 
             match f.write_all(format!("{task}\n").as_bytes()).await {
@@ -160,6 +174,13 @@ impl List {
     pub fn append(&mut self, task: crate::tasks::Task) {
         self.inner.push(task);
     }
+
+    /// Inserts `task` at `index`, or at the end of a shorter list.
+    pub fn insert(&mut self, index: usize, task: crate::tasks::Task) {
+        let index = index.min(self.inner.tasks.len());
+
+        self.inner.tasks.insert(index, task);
+    }
 }
 
 impl std::ops::Deref for List {
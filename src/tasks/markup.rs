@@ -6,55 +6,329 @@ impl Markup for todo_txt::task::Note {
     fn markup(&self) -> Option<String> {
         let content = self.content()?;
 
-        let parser = pulldown_cmark::Parser::new(&content);
+        Some(markdown(&content))
+    }
+}
+
+pub fn options() -> pulldown_cmark::Options {
+    pulldown_cmark::Options::ENABLE_TABLES
+        | pulldown_cmark::Options::ENABLE_STRIKETHROUGH
+        | pulldown_cmark::Options::ENABLE_TASKLISTS
+}
+
+/// Converts Markdown to Pango markup.
+pub fn markdown(content: &str) -> String {
+    let parser = pulldown_cmark::Parser::new_ext(content, options());
 
-        let mut markup = String::from("<markup>");
+    let mut markup = String::from("<markup>");
+    let mut table: Option<Table> = None;
+    // Next number of each nested list, `None` for bullet lists
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    let mut item = 0;
+    let mut quotes = 0;
+    // Pango rejects nested links, like an image inside a link
+    let mut links = 0;
 
-        let headers = [
-            "xx-large", "x-large", "large", "medium", "small", "x-small", "xx-small",
-        ];
+    let headers = [
+        "xx-large", "x-large", "large", "medium", "small", "x-small", "xx-small",
+    ];
 
-        for event in parser {
-            use std::fmt::Write;
+    for event in parser {
+        use std::fmt::Write;
 
-            use pulldown_cmark::Event;
-            use pulldown_cmark::{Tag, TagEnd};
+        use pulldown_cmark::Event;
+        use pulldown_cmark::{Tag, TagEnd};
 
-            match event {
-                Event::Start(Tag::Heading { level, .. }) => {
-                    write!(markup, "<span font_size='{}'><u>", headers[level as usize]).ok();
+        match &event {
+            Event::Start(Tag::Table(_)) => {
+                table = Some(Table::default());
+                continue;
+            }
+            Event::End(TagEnd::Table) => {
+                if let Some(table) = table.take() {
+                    markup.push_str(&table.markup());
                 }
-                Event::End(TagEnd::Heading(..)) => markup.push_str("</u></span>\n\n"),
+                continue;
+            }
+            Event::Start(Tag::TableHead | Tag::TableRow) => {
+                if let Some(table) = &mut table {
+                    table.rows.push(Vec::new());
+                }
+                continue;
+            }
+            Event::End(TagEnd::TableHead) => {
+                if let Some(table) = &mut table {
+                    table.head = table.rows.len();
+                }
+                continue;
+            }
+            Event::Start(Tag::TableCell) => {
+                if let Some(row) = table.as_mut().and_then(|x| x.rows.last_mut()) {
+                    row.push(String::new());
+                }
+                continue;
+            }
+            Event::End(TagEnd::TableRow | TagEnd::TableCell) => continue,
+            _ => (),
+        }
+
+        // Inside a table, everything is written in the current cell
+        let out = match table
+            .as_mut()
+            .and_then(|x| x.rows.last_mut())
+            .and_then(|x| x.last_mut())
+        {
+            Some(cell) => cell,
+            None => &mut markup,
+        };
+
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                write!(out, "<span font_size='{}'><u>", headers[level as usize]).ok();
+            }
+            Event::End(TagEnd::Heading(..)) => out.push_str("</u></span>\n\n"),
+
+            Event::Start(Tag::Paragraph) => {
+                out.push_str("<span>");
+                out.push_str(&"▎ ".repeat(quotes));
+            }
+            Event::End(TagEnd::Paragraph) => out.push_str("</span>\n"),
 
-                Event::Start(Tag::Paragraph) => markup.push_str("<span>"),
-                Event::End(TagEnd::Paragraph) => markup.push_str("</span>\n"),
+            Event::Start(Tag::BlockQuote(_)) => {
+                quotes += 1;
+                out.push_str("<span foreground='gray'><i>");
+            }
+            Event::End(TagEnd::BlockQuote(_)) => {
+                quotes -= 1;
+                out.push_str("</i></span>");
+            }
 
-                Event::Start(Tag::CodeBlock(_)) => markup.push_str("<tt>"),
-                Event::End(TagEnd::CodeBlock) => markup.push_str("</tt>"),
+            Event::Start(Tag::CodeBlock(_)) => out.push_str("<tt>"),
+            Event::End(TagEnd::CodeBlock) => out.push_str("</tt>"),
 
-                Event::Start(Tag::Emphasis) => markup.push_str("<i>"),
-                Event::End(TagEnd::Emphasis) => markup.push_str("</i>"),
+            Event::Start(Tag::Emphasis) => out.push_str("<i>"),
+            Event::End(TagEnd::Emphasis) => out.push_str("</i>"),
 
-                Event::Start(Tag::Strong) => markup.push_str("<b>"),
-                Event::End(TagEnd::Strong) => markup.push_str("</b>"),
+            Event::Start(Tag::Strong) => out.push_str("<b>"),
+            Event::End(TagEnd::Strong) => out.push_str("</b>"),
 
-                Event::Start(Tag::Item) => markup.push_str("· "),
-                Event::End(TagEnd::Item) | Event::SoftBreak => markup.push('\n'),
+            Event::Start(Tag::Strikethrough) => out.push_str("<s>"),
+            Event::End(TagEnd::Strikethrough) => out.push_str("</s>"),
 
-                Event::Start(Tag::Link {
-                    dest_url, title, ..
-                }) => {
-                    write!(markup, "<a href='{dest_url}' title='{title}'>").ok();
+            Event::Start(Tag::List(start)) => {
+                if !lists.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
                 }
-                Event::End(TagEnd::Link) => markup.push_str("</a>"),
+                lists.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+            }
+
+            Event::Start(Tag::Item) => {
+                out.push_str(&"    ".repeat(lists.len().saturating_sub(1)));
+                item = out.len();
+
+                match lists.last_mut() {
+                    Some(Some(n)) => {
+                        write!(out, "{n}. ").ok();
+                        *n += 1;
+                    }
+                    _ => out.push_str("· "),
+                }
+            }
+            Event::End(TagEnd::Item) if !out.ends_with('\n') => out.push('\n'),
+            Event::TaskListMarker(checked) => {
+                out.truncate(item);
+                out.push_str(if checked { "☑ " } else { "☐ " });
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                out.push('\n');
+                out.push_str(&"▎ ".repeat(quotes));
+            }
+            Event::Rule => out.push_str("──────────\n"),
+
+            Event::Start(Tag::Link {
+                dest_url, title, ..
+            })
+            | Event::Start(Tag::Image {
+                dest_url, title, ..
+            }) => {
+                links += 1;
+
+                if links > 1 {
+                    continue;
+                }
+
+                write!(out, "<a href=\"{}\"", escape(&dest_url)).ok();
+
+                if !title.is_empty() {
+                    write!(out, " title=\"{}\"", escape(&title)).ok();
+                }
+
+                out.push('>');
+            }
+            Event::End(TagEnd::Link | TagEnd::Image) => {
+                links -= 1;
+
+                if links == 0 {
+                    out.push_str("</a>");
+                }
+            }
+
+            Event::Code(code) => {
+                write!(out, "<tt>{}</tt>", escape(&code)).ok();
+            }
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                out.push_str(&escape(&text))
+            }
+
+            _ => (),
+        }
+    }
+    markup.push_str("</markup>");
 
-                Event::Text(t) => markup.push_str(&t.replace('&', "&amp;")),
+    markup
+}
 
-                _ => (),
+#[derive(Default)]
+struct Table {
+    rows: Vec<Vec<String>>,
+    head: usize,
+}
+
+impl Table {
+    /// Pango can't draw tables, columns are aligned with a monospace font.
+    fn markup(&self) -> String {
+        let columns = self.rows.iter().map(Vec::len).max().unwrap_or_default();
+        let mut widths = vec![0; columns];
+
+        for row in &self.rows {
+            for (n, cell) in row.iter().enumerate() {
+                widths[n] = widths[n].max(visible_len(cell));
             }
         }
-        markup.push_str("</markup>");
 
-        Some(markup)
+        let mut markup = String::from("<tt>");
+
+        for (n, row) in self.rows.iter().enumerate() {
+            let cells = widths
+                .iter()
+                .enumerate()
+                .map(|(x, width)| {
+                    let cell = row.get(x).map(String::as_str).unwrap_or_default();
+                    let padding = " ".repeat(width - visible_len(cell));
+
+                    if n < self.head {
+                        format!("<b>{cell}</b>{padding}")
+                    } else {
+                        format!("{cell}{padding}")
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            markup.push_str(&cells.join(" │ "));
+            markup.push('\n');
+
+            if n + 1 == self.head {
+                let separator = widths.iter().map(|x| "─".repeat(*x)).collect::<Vec<_>>();
+
+                markup.push_str(&separator.join("─┼─"));
+                markup.push('\n');
+            }
+        }
+
+        markup.push_str("</tt>\n");
+
+        markup
+    }
+}
+
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Length of the text displayed for a piece of markup.
+fn visible_len(markup: &str) -> usize {
+    static TAG: std::sync::LazyLock<regex::Regex> =
+        std::sync::LazyLock::new(|| regex::Regex::new(r"<[^>]*>").unwrap());
+    static ENTITY: std::sync::LazyLock<regex::Regex> =
+        std::sync::LazyLock::new(|| regex::Regex::new(r"&[#\w]+;").unwrap());
+
+    let text = TAG.replace_all(markup, "");
+
+    ENTITY.replace_all(&text, "_").chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn escape() {
+        assert_eq!(
+            super::markdown("a & b < c > \"d\" 'e' <script>x</script>"),
+            "<markup><span>a &amp; b &lt; c &gt; &quot;d&quot; &apos;e&apos; &lt;script&gt;x&lt;/script&gt;</span>\n</markup>"
+        );
+        assert_eq!(
+            super::markdown("<div>\n<b>html</b>\n</div>"),
+            "<markup>&lt;div&gt;\n&lt;b&gt;html&lt;/b&gt;\n&lt;/div&gt;</markup>"
+        );
+    }
+
+    #[test]
+    fn code() {
+        assert_eq!(
+            super::markdown("Use `Vec<u8>` & co\n\n```\nif a < b {}\n```"),
+            "<markup><span>Use <tt>Vec&lt;u8&gt;</tt> &amp; co</span>\n<tt>if a &lt; b {}\n</tt></markup>"
+        );
+    }
+
+    #[test]
+    fn lists() {
+        assert_eq!(
+            super::markdown("3. three\n4. four\n   - sub\n     1. deep\n5. five"),
+            "<markup>3. three\n4. four\n    · sub\n        1. deep\n5. five\n</markup>"
+        );
+        assert_eq!(super::markdown("1. [ ] todo"), "<markup>☐ todo\n</markup>");
+    }
+
+    #[test]
+    fn links() {
+        assert_eq!(
+            super::markdown("[x](http://a?b=\"c\"&d 'T<\\'s>') ![alt](img.png)"),
+            "<markup><span><a href=\"http://a?b=&quot;c&quot;&amp;d\" title=\"T&lt;&apos;s&gt;\">x</a> <a href=\"img.png\">alt</a></span>\n</markup>"
+        );
+        assert_eq!(
+            super::markdown("[![badge](badge.png)](http://a)"),
+            "<markup><span><a href=\"http://a\">badge</a></span>\n</markup>"
+        );
+    }
+
+    #[test]
+    fn extended() {
+        assert_eq!(
+            super::markdown("- [x] done\n- [ ] ~~todo~~\n  - nested\n\n> quoted\n> text"),
+            "<markup>☑ done\n☐ <s>todo</s>\n    · nested\n<span foreground='gray'><i><span>▎ quoted\n▎ text</span>\n</i></span></markup>"
+        );
+    }
+
+    #[test]
+    fn table() {
+        assert_eq!(
+            super::markdown("| a | b |\n|---|---|\n| **long** | x |"),
+            "<markup><tt><b>a</b>    │ <b>b</b>\n─────┼──\n<b>long</b> │ x\n</tt>\n</markup>"
+        );
     }
 }
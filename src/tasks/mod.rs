@@ -1,6 +1,19 @@
+pub mod attachment;
+pub mod bulk;
+pub mod completion;
+pub mod dashboard;
+pub mod estimate;
+pub mod index;
 mod list;
 pub mod markup;
+pub mod natural;
+pub mod next;
+pub mod project;
+pub mod rename;
+pub mod review;
+pub mod stats;
 pub mod task;
+pub mod tracking;
 
 pub use list::List;
 pub use markup::Markup;
@@ -0,0 +1,427 @@
+use chrono::Datelike as _;
+
+/// Fields recognized in a task typed in plain language, like
+/// `Call Bob tomorrow at 3pm !A @phone every monday`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Parsed {
+    pub subject: String,
+    pub priority: Option<char>,
+    pub due: Option<chrono::NaiveDate>,
+    pub time: Option<chrono::NaiveTime>,
+    pub recurrence: Option<todo_txt::task::Recurrence>,
+}
+
+impl Parsed {
+    /// The todo.txt line of the task.
+    pub fn line(&self) -> String {
+        let mut line = String::new();
+
+        if let Some(priority) = self.priority {
+            line.push_str(&format!("({priority}) "));
+        }
+
+        line.push_str(&self.subject);
+
+        if let Some(due) = self.due {
+            line.push_str(&format!(" due:{}", due.format("%Y-%m-%d")));
+        }
+
+        if let Some(time) = self.time {
+            line.push_str(&format!(
+                " {}:{}",
+                super::task::TIME_TAG,
+                crate::date::format_time(time)
+            ));
+        }
+
+        if let Some(recurrence) = &self.recurrence {
+            line.push_str(&format!(" rec:{recurrence}"));
+        }
+
+        line
+    }
+
+    /// Human readable list of the recognized fields.
+    pub fn summary(&self) -> String {
+        let mut fields = Vec::new();
+
+        if let Some(priority) = self.priority {
+            fields.push(format!("priority {priority}"));
+        }
+
+        if let Some(due) = self.due {
+            fields.push(format!("due {}", due.format("%a %Y-%m-%d")));
+        }
+
+        if let Some(time) = self.time {
+            fields.push(format!("at {}", crate::date::format_time(time)));
+        }
+
+        if let Some(recurrence) = &self.recurrence {
+            fields.push(format!("every {recurrence}"));
+        }
+
+        fields.join(" · ")
+    }
+}
+
+/// Dates, times and recurrences are recognized after a keyword (`due friday`,
+/// `at 3pm`, `every monday`), a bare date (`friday`) only ends the text: in
+/// `Prepare friday slides`, it's part of the subject.
+pub fn parse(text: &str, today: chrono::NaiveDate) -> Parsed {
+    let words = text.split_whitespace().collect::<Vec<_>>();
+    let mut parsed = Parsed::default();
+    let mut subject = Vec::new();
+    let mut n = 0;
+
+    while n < words.len() {
+        if let Some((field, len)) = field(&words[n..], today) {
+            match field {
+                Field::Priority(priority) => parsed.priority = Some(priority),
+                Field::Due(date) => parsed.due = Some(date),
+                Field::Time(time) => parsed.time = Some(time),
+                Field::Recurrence(recurrence, first) => {
+                    parsed.recurrence = Some(recurrence);
+
+                    if first.is_some() {
+                        parsed.due = first;
+                    }
+                }
+            }
+            n += len;
+        } else if let Some((date, len)) = date(&words[n..], today)
+            && trailing(&words[n + len..], today)
+        {
+            parsed.due = Some(date);
+            n += len;
+        } else {
+            subject.push(words[n]);
+            n += 1;
+        }
+    }
+
+    if parsed.due.is_none() && (parsed.time.is_some() || parsed.recurrence.is_some()) {
+        parsed.due = Some(today);
+    }
+
+    // The recognized fields replace the ones written as todo.txt tags
+    subject.retain(|word| {
+        let replaced = |tag: &str| word.starts_with(&format!("{tag}:"));
+
+        !(parsed.due.is_some() && replaced("due")
+            || parsed.time.is_some() && replaced(super::task::TIME_TAG)
+            || parsed.recurrence.is_some() && replaced("rec"))
+    });
+
+    if parsed.priority.is_some()
+        && subject
+            .first()
+            .is_some_and(|x| x.len() == 3 && x.starts_with('(') && x.ends_with(')'))
+    {
+        subject.remove(0);
+    }
+
+    parsed.subject = subject.join(" ");
+
+    parsed
+}
+
+enum Field {
+    Priority(char),
+    Due(chrono::NaiveDate),
+    Time(chrono::NaiveTime),
+    Recurrence(todo_txt::task::Recurrence, Option<chrono::NaiveDate>),
+}
+
+/// A field introduced by a keyword at the start of `words`, with the number of
+/// words read.
+fn field(words: &[&str], today: chrono::NaiveDate) -> Option<(Field, usize)> {
+    let word = words.first()?.to_lowercase();
+    let next = words.get(1).map(|x| x.to_lowercase());
+
+    if let Some(priority) = priority(&word) {
+        Some((Field::Priority(priority), 1))
+    } else if matches!(word.as_str(), "on" | "by" | "due")
+        && let Some((date, len)) = date(&words[1..], today)
+    {
+        Some((Field::Due(date), len + 1))
+    } else if word == "at"
+        && let Some(time) = next.as_deref().and_then(time)
+    {
+        Some((Field::Time(time), 2))
+    } else if let Some((recurrence, first, len)) = recurrence(words, today) {
+        Some((Field::Recurrence(recurrence, first), len))
+    } else {
+        None
+    }
+}
+
+/// Whether `words` only contains fields, tags and dates.
+fn trailing(words: &[&str], today: chrono::NaiveDate) -> bool {
+    let mut n = 0;
+
+    while n < words.len() {
+        if is_tag(words[n]) {
+            n += 1;
+        } else if let Some((_, len)) = field(&words[n..], today) {
+            n += len;
+        } else if let Some((_, len)) = date(&words[n..], today) {
+            n += len;
+        } else {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// `+project`, `@context`, `#hashtag` or `key:value`.
+fn is_tag(word: &str) -> bool {
+    if word.len() > 1 && word.starts_with(['+', '@', '#']) {
+        return true;
+    }
+
+    word.split_once(':')
+        .is_some_and(|(key, value)| !key.is_empty() && !value.is_empty())
+}
+
+/// `!A` to `!Z`.
+fn priority(word: &str) -> Option<char> {
+    let mut chars = word.strip_prefix('!')?.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => Some(c.to_ascii_uppercase()),
+        _ => None,
+    }
+}
+
+/// A date at the start of `words`, with the number of words read.
+fn date(words: &[&str], today: chrono::NaiveDate) -> Option<(chrono::NaiveDate, usize)> {
+    let word = |n: usize| words.get(n).map(|x| x.to_lowercase());
+
+    match word(0)?.as_str() {
+        "today" | "tonight" => Some((today, 1)),
+        "tomorrow" => Some((today + chrono::Duration::days(1), 1)),
+        "next" => match word(1)?.as_str() {
+            "week" => Some((today + chrono::Duration::weeks(1), 2)),
+            "month" => Some((add(today, 1, todo_txt::task::Period::Month)?, 2)),
+            "year" => Some((add(today, 1, todo_txt::task::Period::Year)?, 2)),
+            x => Some((next_weekday(weekday(x)?, today), 2)),
+        },
+        "in" => {
+            let num = word(1)?.parse().ok()?;
+
+            Some((add(today, num, unit(&word(2)?)?)?, 3))
+        }
+        x => {
+            if let Ok(date) = chrono::NaiveDate::parse_from_str(x, "%Y-%m-%d") {
+                return Some((date, 1));
+            }
+
+            Some((next_weekday(weekday(x)?, today), 1))
+        }
+    }
+}
+
+/// A recurrence at the start of `words`, with its first occurrence when it
+/// falls on a given weekday and the number of words read.
+fn recurrence(
+    words: &[&str],
+    today: chrono::NaiveDate,
+) -> Option<(todo_txt::task::Recurrence, Option<chrono::NaiveDate>, usize)> {
+    let word = |n: usize| words.get(n).map(|x| x.to_lowercase());
+
+    match word(0)?.as_str() {
+        "every" | "each" => {
+            let next = word(1)?;
+
+            if let Some(weekday) = weekday(&next) {
+                return Some((
+                    period(1, todo_txt::task::Period::Week),
+                    Some(next_weekday(weekday, today)),
+                    2,
+                ));
+            }
+
+            if let Some(unit) = unit(&next) {
+                return Some((period(1, unit), None, 2));
+            }
+
+            let num = next.parse().ok()?;
+            let unit = unit(&word(2)?)?;
+            // The next occurrences are computed by todo-txt, which overflows
+            add(today, num, unit)?;
+
+            Some((period(num, unit), None, 3))
+        }
+        _ => None,
+    }
+}
+
+/// `date` moved `num` periods later, `None` for a non-positive `num` or out
+/// of range dates.
+fn add(
+    date: chrono::NaiveDate,
+    num: i64,
+    period: todo_txt::task::Period,
+) -> Option<chrono::NaiveDate> {
+    use todo_txt::task::Period::*;
+
+    let num = u32::try_from(num).ok().filter(|x| *x > 0)?;
+
+    match period {
+        Day => date.checked_add_days(chrono::Days::new(num.into())),
+        Week => date.checked_add_days(chrono::Days::new(u64::from(num) * 7)),
+        Month => date.checked_add_months(chrono::Months::new(num)),
+        Year => date.checked_add_months(chrono::Months::new(num.checked_mul(12)?)),
+    }
+}
+
+fn period(num: i64, period: todo_txt::task::Period) -> todo_txt::task::Recurrence {
+    todo_txt::task::Recurrence {
+        num,
+        period,
+        strict: false,
+    }
+}
+
+fn unit(word: &str) -> Option<todo_txt::task::Period> {
+    use todo_txt::task::Period::*;
+
+    let unit = match word.trim_end_matches('s') {
+        "day" => Day,
+        "week" => Week,
+        "month" => Month,
+        "year" => Year,
+        _ => return None,
+    };
+
+    Some(unit)
+}
+
+fn weekday(word: &str) -> Option<chrono::Weekday> {
+    use chrono::Weekday::*;
+
+    let weekday = match word {
+        "monday" => Mon,
+        "tuesday" => Tue,
+        "wednesday" => Wed,
+        "thursday" => Thu,
+        "friday" => Fri,
+        "saturday" => Sat,
+        "sunday" => Sun,
+        _ => return None,
+    };
+
+    Some(weekday)
+}
+
+/// The first `weekday` after `today`.
+fn next_weekday(weekday: chrono::Weekday, today: chrono::NaiveDate) -> chrono::NaiveDate {
+    let days = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+
+    today + chrono::Duration::days(if days == 0 { 7 } else { days.into() })
+}
+
+/// `3pm`, `3:30pm`, `15:30` or `15h`.
+fn time(word: &str) -> Option<chrono::NaiveTime> {
+    let (value, offset) = if let Some(value) = word.strip_suffix("pm") {
+        (value, 12)
+    } else if let Some(value) = word.strip_suffix("am") {
+        (value, 0)
+    } else {
+        return crate::date::parse_time(word);
+    };
+
+    let (hour, minute) = value.split_once(':').unwrap_or((value, "0"));
+    let hour = hour.parse::<u32>().ok().filter(|x| (1..=12).contains(x))? % 12 + offset;
+
+    chrono::NaiveTime::from_hms_opt(hour, minute.parse().ok()?, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    // A Monday
+    fn today() -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(2042, 1, 6).unwrap()
+    }
+
+    fn line(text: &str) -> String {
+        super::parse(text, today()).line()
+    }
+
+    #[test]
+    fn parse() {
+        let parsed = super::parse("Call Bob tomorrow at 3pm !a @phone +work", today());
+        assert_eq!(parsed.subject, "Call Bob @phone +work");
+        assert_eq!(
+            parsed.line(),
+            "(A) Call Bob @phone +work due:2042-01-07 at:15:00"
+        );
+
+        let parsed = super::parse("Weekly review every monday #gtd", today());
+        assert_eq!(parsed.line(), "Weekly review #gtd due:2042-01-13 rec:1w");
+        assert_eq!(parsed.summary(), "due Mon 2042-01-13 · every 1w");
+
+        assert_eq!(
+            line("Meet at office in 2 weeks"),
+            "Meet at office due:2042-01-20"
+        );
+        assert_eq!(
+            line("Pay rent every 1 month due 2042-02-01"),
+            "Pay rent due:2042-02-01 rec:1m"
+        );
+        assert_eq!(
+            line("Backup at 12am every day"),
+            "Backup due:2042-01-06 at:00:00 rec:1d"
+        );
+        assert_eq!(
+            line("Plain (B) task due:2042-01-01"),
+            "Plain (B) task due:2042-01-01"
+        );
+    }
+
+    #[test]
+    fn bare_dates() {
+        assert_eq!(line("Prepare friday slides"), "Prepare friday slides");
+        assert_eq!(line("Watch today show on tv"), "Watch today show on tv");
+        assert_eq!(
+            line("Prepare slides friday"),
+            "Prepare slides due:2042-01-10"
+        );
+        assert_eq!(
+            line("Prepare slides by friday for the meeting"),
+            "Prepare slides for the meeting due:2042-01-10"
+        );
+        assert_eq!(line("Lunch at 12"), "Lunch at 12");
+        assert_eq!(
+            line("Lunch at noon tomorrow"),
+            "Lunch at noon due:2042-01-07"
+        );
+    }
+
+    #[test]
+    fn replaced_tags() {
+        assert_eq!(line("Task due:2042-01-01 tomorrow"), "Task due:2042-01-07");
+        assert_eq!(line("(B) Task !a"), "(A) Task");
+        assert_eq!(line("Task rec:1d every week"), "Task due:2042-01-06 rec:1w");
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(line("Wait in 100000000 days"), "Wait in 100000000 days");
+        assert_eq!(
+            line("Wait in 9223372036854775807 years"),
+            "Wait in 9223372036854775807 years"
+        );
+        assert_eq!(line("Wait in 0 days"), "Wait in 0 days");
+        assert_eq!(line("Wait in -2 weeks"), "Wait in -2 weeks");
+        assert_eq!(line("Run every 0 days"), "Run every 0 days");
+        assert_eq!(line("Run every -1 weeks"), "Run every -1 weeks");
+        assert_eq!(
+            line("Run every 99999999999 months"),
+            "Run every 99999999999 months"
+        );
+        assert_eq!(line("Run every 2 years"), "Run due:2042-01-06 rec:2y");
+    }
+}
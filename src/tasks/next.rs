@@ -0,0 +1,195 @@
+/// A task with `dep:<id>` is blocked while an open task has `id:<id>`.
+pub const ID_TAG: &str = "id";
+pub const DEPENDENCY_TAG: &str = "dep";
+
+/// How the next action of a project is chosen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rules {
+    /// Ignores tasks deferred after today.
+    pub deferred: bool,
+    /// Ignores tasks waiting for another open task.
+    pub blocked: bool,
+    /// Ignores hidden tasks.
+    pub hidden: bool,
+    /// Prefers the soonest due date over the highest priority.
+    pub due_first: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            deferred: true,
+            blocked: true,
+            hidden: true,
+            due_first: false,
+        }
+    }
+}
+
+/// The next action of each active project, `None` when nothing can be done.
+pub fn next_actions(
+    tasks: &[crate::tasks::Task],
+    projects: &crate::tasks::project::Projects,
+    today: chrono::NaiveDate,
+    rules: Rules,
+) -> Vec<(String, Option<crate::tasks::Task>)> {
+    use crate::tasks::project::Status;
+
+    let open = tasks.iter().filter(|x| !x.finished).collect::<Vec<_>>();
+    let ids = open
+        .iter()
+        .filter_map(|x| x.tags.get(ID_TAG).map(String::as_str))
+        .collect::<std::collections::BTreeSet<_>>();
+
+    let names = open
+        .iter()
+        .flat_map(|x| x.projects.iter())
+        .filter(|x| {
+            projects
+                .get(*x)
+                .is_none_or(|metadata| metadata.status == Status::Active)
+        })
+        .collect::<std::collections::BTreeSet<_>>();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let next = open
+                .iter()
+                .filter(|x| x.projects.contains(name))
+                .filter(|x| {
+                    (!rules.deferred || x.threshold_date.is_none_or(|date| date <= today))
+                        && (!rules.blocked || !is_blocked(x, &ids))
+                        && (!rules.hidden || !x.hidden)
+                })
+                .min_by(|a, b| compare(a, b, rules))
+                .map(|x| (*x).clone());
+
+            (name.clone(), next)
+        })
+        .collect()
+}
+
+fn is_blocked(task: &crate::tasks::Task, ids: &std::collections::BTreeSet<&str>) -> bool {
+    task.tags
+        .get(DEPENDENCY_TAG)
+        .is_some_and(|dependencies| dependencies.split(',').any(|x| ids.contains(x)))
+}
+
+/// The best candidate first.
+fn compare(a: &crate::tasks::Task, b: &crate::tasks::Task, rules: Rules) -> std::cmp::Ordering {
+    let priority = b.priority.cmp(&a.priority);
+    // Tasks without due date come last
+    let due = a
+        .due_date
+        .is_none()
+        .cmp(&b.due_date.is_none())
+        .then_with(|| a.due_date.cmp(&b.due_date));
+
+    if rules.due_first {
+        due.then(priority)
+    } else {
+        priority.then(due)
+    }
+    .then_with(|| a.id.cmp(&b.id))
+}
+
+#[cfg(test)]
+mod tests {
+    /// Tasks with their index as id, like in the list.
+    fn tasks(lines: &[&str]) -> Vec<crate::tasks::Task> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(id, line)| {
+                let mut task = crate::tasks::Task::from(line.to_string());
+                task.id = id;
+                task
+            })
+            .collect()
+    }
+
+    fn book() -> Vec<crate::tasks::Task> {
+        tasks(&[
+            "Outline +book",
+            "(B) Write +book due:2042-02-01",
+            "(A) Publish +book dep:write",
+            "(C) Print +book due:2042-01-20",
+            "(A) Paint +house t:2042-01-20",
+            "Call +garden",
+            "Edit +book id:write",
+            "x Plant +orchard",
+        ])
+    }
+
+    fn actions(tasks: &[crate::tasks::Task], rules: super::Rules) -> Vec<(String, Option<String>)> {
+        let today = chrono::NaiveDate::from_ymd_opt(2042, 1, 15).unwrap();
+        let mut projects = crate::tasks::project::Projects::new();
+        projects.insert(
+            "garden".to_string(),
+            crate::tasks::project::Metadata {
+                status: crate::tasks::project::Status::Someday,
+                ..Default::default()
+            },
+        );
+
+        super::next_actions(tasks, &projects, today, rules)
+            .into_iter()
+            .map(|(name, task)| (name, task.map(|x| x.subject.clone())))
+            .collect()
+    }
+
+    fn next(name: &str, subject: Option<&str>) -> (String, Option<String>) {
+        (name.to_string(), subject.map(str::to_string))
+    }
+
+    #[test]
+    fn next_actions() {
+        let rules = super::Rules::default();
+
+        assert_eq!(
+            actions(&book(), rules),
+            [next("book", Some("Write +book")), next("house", None)]
+        );
+        assert_eq!(
+            actions(
+                &tasks(&["(A) Publish +book dep:write", "x Edit +book id:write"]),
+                rules
+            ),
+            [next("book", Some("Publish +book"))]
+        );
+        assert_eq!(
+            actions(&tasks(&["Outline +book", "Draft +book", "Call"]), rules),
+            [next("book", Some("Outline +book"))]
+        );
+        assert!(actions(&[], rules).is_empty());
+        assert!(actions(&tasks(&["x Plant +orchard"]), rules).is_empty());
+    }
+
+    #[test]
+    fn rules() {
+        let rules = super::Rules {
+            blocked: false,
+            deferred: false,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            actions(&book(), rules),
+            [
+                next("book", Some("Publish +book")),
+                next("house", Some("Paint +house")),
+            ]
+        );
+
+        let rules = super::Rules {
+            due_first: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            actions(&book(), rules)[0],
+            next("book", Some("Print +book"))
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use serde_json::Value;
+
+/// Projects metadata, by project name.
+pub type Projects = std::collections::BTreeMap<String, Metadata>;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Status {
+    #[default]
+    Active,
+    Waiting,
+    Someday,
+    Done,
+}
+
+impl Status {
+    pub const ALL: [Self; 4] = [Self::Active, Self::Waiting, Self::Someday, Self::Done];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Waiting => "waiting",
+            Self::Someday => "someday",
+            Self::Done => "done",
+        }
+    }
+}
+
+impl std::str::FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|x| x.as_str() == s)
+            .ok_or_else(|| format!("Invalid project status: {s}"))
+    }
+}
+
+/// What describes a project, beside its tasks.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metadata {
+    pub description: String,
+    pub deadline: Option<chrono::NaiveDate>,
+    pub status: Status,
+    /// A CSS color, like `#3584e4`.
+    pub color: Option<String>,
+    /// Last weekly review.
+    pub reviewed: Option<chrono::NaiveDate>,
+}
+
+impl Metadata {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Human readable status, deadline and last review.
+    pub fn summary(&self) -> String {
+        let mut summary = self.status.as_str().to_string();
+
+        if let Some(deadline) = self.deadline {
+            summary.push_str(&format!(" · deadline {}", deadline.format("%Y-%m-%d")));
+        }
+
+        if let Some(reviewed) = self.reviewed {
+            summary.push_str(&format!(" · reviewed {}", reviewed.format("%Y-%m-%d")));
+        }
+
+        summary
+    }
+}
+
+/// The sidecar file, `$EFFITASK_PROJECTS_FILE` or `$TODO_DIR/projects.json`.
+pub fn path() -> std::path::PathBuf {
+    match std::env::var("EFFITASK_PROJECTS_FILE") {
+        Ok(path) => path.into(),
+        Err(_) => std::path::Path::new(&std::env::var("TODO_DIR").unwrap_or_default())
+            .join("projects.json"),
+    }
+}
+
+pub fn load() -> Projects {
+    let path = path();
+
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return Projects::new();
+    };
+
+    parse(&contents).unwrap_or_else(|err| {
+        log::error!("Unable to read {}: {err}", path.display());
+        Projects::new()
+    })
+}
+
+pub fn save(projects: &Projects) -> Result<(), String> {
+    let path = path();
+
+    std::fs::write(&path, render(projects))
+        .map_err(|err| format!("Unable to write {}: {err}", path.display()))
+}
+
+pub fn parse(contents: &str) -> Result<Projects, String> {
+    let Value::Object(fields) = serde_json::from_str(contents).map_err(|err| err.to_string())?
+    else {
+        return Err("a JSON object is expected".to_string());
+    };
+
+    let mut projects = Projects::new();
+
+    for (name, value) in fields {
+        let str = |key| value.get(key).and_then(Value::as_str);
+
+        let date = |key| {
+            str(key)
+                .map(|x| chrono::NaiveDate::parse_from_str(x, "%Y-%m-%d"))
+                .transpose()
+                .map_err(|err| format!("{name}: {err}"))
+        };
+
+        let metadata = Metadata {
+            description: str("description").unwrap_or_default().to_string(),
+            deadline: date("deadline")?,
+            status: str("status")
+                .map(str::parse)
+                .transpose()?
+                .unwrap_or_default(),
+            color: str("color").map(String::from),
+            reviewed: date("reviewed")?,
+        };
+
+        projects.insert(name, metadata);
+    }
+
+    Ok(projects)
+}
+
+pub fn render(projects: &Projects) -> String {
+    let date = |value: Option<chrono::NaiveDate>| value.map(|x| x.format("%Y-%m-%d").to_string());
+
+    let fields = projects
+        .iter()
+        .filter(|(_, metadata)| !metadata.is_empty())
+        .map(|(name, metadata)| {
+            let value = serde_json::json!({
+                "description": metadata.description,
+                "deadline": date(metadata.deadline),
+                "status": metadata.status.as_str(),
+                "color": metadata.color,
+                "reviewed": date(metadata.reviewed),
+            });
+
+            (name.clone(), value)
+        })
+        .collect();
+
+    format!("{}\n", Value::Object(fields))
+}
+
+/// Follows a project (and its sub-projects) renamed, moved or merged, the
+/// metadata already set on the target wins.
+pub fn rename(projects: &mut Projects, from: &str, to: &str) {
+    let renamed = projects
+        .keys()
+        .filter_map(|x| Some((x.clone(), super::rename::renamed(x, from, to)?)))
+        .collect::<Vec<_>>();
+
+    for (old, new) in renamed {
+        if let Some(metadata) = projects.remove(&old) {
+            projects.entry(new).or_insert(metadata);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse() {
+        let mut projects = super::parse(
+            r##"{"work": {"description": "Day job", "deadline": "2042-01-01", "status": "waiting", "color": "#3584e4"}, "work\\admin": {"status": "done", "reviewed": "2042-01-03"}}"##,
+        )
+        .unwrap();
+
+        assert_eq!(projects["work"].description, "Day job");
+        assert_eq!(projects["work"].status, super::Status::Waiting);
+        assert_eq!(projects["work"].color.as_deref(), Some("#3584e4"));
+        assert_eq!(projects["work\\admin"].status, super::Status::Done);
+        assert_eq!(
+            projects["work\\admin"].summary(),
+            "done · reviewed 2042-01-03"
+        );
+        assert_eq!(super::parse(&super::render(&projects)).unwrap(), projects);
+
+        super::rename(&mut projects, "work", "job");
+        assert_eq!(
+            projects.keys().collect::<Vec<_>>(),
+            vec!["job", "job\\admin"]
+        );
+
+        assert!(super::parse(r#"{"work": {"status": "unknown"}}"#).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+/// Sub-projects (or sub-contexts) separator.
+pub const SEPARATOR: char = '\\';
+
+/// What to do with a tag, from its tree.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Change {
+    /// Changes the last part of the name.
+    Rename,
+    /// Moves under another parent, at the top level for an empty one.
+    Move,
+    /// Merges into another tag.
+    Merge,
+}
+
+impl Change {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Rename => "Rename",
+            Self::Move => "Move",
+            Self::Merge => "Merge",
+        }
+    }
+
+    pub fn placeholder(&self) -> &'static str {
+        match self {
+            Self::Rename => "New name",
+            Self::Move => "New parent, empty for top level",
+            Self::Merge => "Existing name",
+        }
+    }
+
+    /// The new name of `from` for the `value` typed by the user, `None` when
+    /// it's invalid or unchanged.
+    pub fn target(&self, prefix: char, from: &str, value: &str) -> Option<String> {
+        let value = value.trim().trim_start_matches(prefix);
+
+        let to = match self {
+            Self::Rename => match from.rsplit_once(SEPARATOR) {
+                Some((parent, _)) => format!("{parent}{SEPARATOR}{value}"),
+                None => value.to_string(),
+            },
+            Self::Move => {
+                let name = from.rsplit(SEPARATOR).next().unwrap_or(from);
+
+                if value.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{value}{SEPARATOR}{name}")
+                }
+            }
+            Self::Merge => value.to_string(),
+        };
+
+        let invalid = (value.is_empty() && *self != Self::Move)
+            || to.contains(char::is_whitespace)
+            || to == from
+            || matches(&to, from);
+
+        (!invalid).then_some(to)
+    }
+}
+
+/// Whether `tag` is `name` or one of its sub-tags.
+pub fn matches(tag: &str, name: &str) -> bool {
+    renamed(tag, name, "").is_some()
+}
+
+/// Tasks using the `prefix` tag `name` or one of its sub-tags.
+pub fn tagged<'a>(
+    tasks: &'a [crate::tasks::Task],
+    prefix: char,
+    name: &str,
+) -> Vec<&'a crate::tasks::Task> {
+    tasks
+        .iter()
+        .filter(|x| tags(x, prefix).iter().any(|x| matches(x, name)))
+        .collect()
+}
+
+/// Renames the `prefix` tag `from` and its sub-tags to `to` in every task,
+/// this also moves or merges them. Returns the number of changed tasks.
+pub fn rename(tasks: &mut [crate::tasks::Task], prefix: char, from: &str, to: &str) -> usize {
+    let mut changed = 0;
+
+    for task in tasks.iter_mut() {
+        let mut words = Vec::new();
+        let mut modified = false;
+
+        for word in task.subject.split_whitespace() {
+            let word = match word.strip_prefix(prefix).and_then(|x| renamed(x, from, to)) {
+                Some(tag) => {
+                    modified = true;
+                    format!("{prefix}{tag}")
+                }
+                None => word.to_string(),
+            };
+
+            // Merging can produce the same tag twice
+            if !(modified && word.starts_with(prefix) && words.contains(&word)) {
+                words.push(word);
+            }
+        }
+
+        if !modified {
+            continue;
+        }
+
+        task.subject = words.join(" ");
+
+        let tags = tags_mut(task, prefix);
+        let mut new_tags = Vec::new();
+
+        for tag in tags.iter() {
+            let tag = renamed(tag, from, to).unwrap_or_else(|| tag.clone());
+
+            if !new_tags.contains(&tag) {
+                new_tags.push(tag);
+            }
+        }
+
+        *tags = new_tags;
+        changed += 1;
+    }
+
+    changed
+}
+
+/// `tag` with its `from` part replaced by `to`, if it's `from` or one of its
+/// sub-tags.
+pub fn renamed(tag: &str, from: &str, to: &str) -> Option<String> {
+    let rest = tag.strip_prefix(from)?;
+
+    if rest.is_empty() || rest.starts_with(SEPARATOR) {
+        Some(format!("{to}{rest}"))
+    } else {
+        None
+    }
+}
+
+fn tags(task: &crate::tasks::Task, prefix: char) -> &[String] {
+    match prefix {
+        '+' => &task.projects,
+        '@' => &task.contexts,
+        _ => &task.hashtags,
+    }
+}
+
+fn tags_mut(task: &mut crate::tasks::Task, prefix: char) -> &mut Vec<String> {
+    match prefix {
+        '+' => &mut task.projects,
+        '@' => &mut task.contexts,
+        _ => &mut task.hashtags,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn rename() {
+        let mut tasks = [
+            "Call +work\\admin @phone",
+            "Sort +work\\admin\\papers +work",
+            "Fix +workshop",
+            "Merge +perso +work\\admin",
+            "Keep +queue +work\\admin order",
+        ]
+        .map(|x| crate::tasks::Task::from(x.to_string()));
+
+        assert!(super::matches("work\\admin\\papers", "work\\admin"));
+        assert!(!super::matches("workshop", "work"));
+        assert_eq!(super::tagged(&tasks, '+', "work\\admin").len(), 4);
+        assert_eq!(super::rename(&mut tasks, '+', "work\\admin", "perso"), 4);
+
+        assert_eq!(tasks[0].subject, "Call +perso @phone");
+        assert_eq!(tasks[1].subject, "Sort +perso\\papers +work");
+        assert_eq!(tasks[1].projects, vec!["work", "perso\\papers"]);
+        assert_eq!(tasks[2].subject, "Fix +workshop");
+        assert_eq!(tasks[3].subject, "Merge +perso");
+        assert_eq!(tasks[3].projects, vec!["perso"]);
+        assert_eq!(tasks[4].subject, "Keep +queue +perso order");
+        assert_eq!(tasks[4].projects, vec!["queue", "perso"]);
+    }
+
+    #[test]
+    fn target() {
+        use super::Change::*;
+
+        assert_eq!(
+            Rename.target('+', "work\\admin", "+office"),
+            Some("work\\office".to_string())
+        );
+        assert_eq!(
+            Move.target('+', "work\\admin", "perso"),
+            Some("perso\\admin".to_string())
+        );
+        assert_eq!(
+            Move.target('+', "work\\admin", ""),
+            Some("admin".to_string())
+        );
+        assert_eq!(Merge.target('+', "work", "job"), Some("job".to_string()));
+
+        assert_eq!(Rename.target('+', "work", ""), None);
+        assert_eq!(Rename.target('+', "work", "day job"), None);
+        assert_eq!(Merge.target('+', "work", "work"), None);
+        assert_eq!(Move.target('+', "work", "work\\admin"), None);
+    }
+}
@@ -0,0 +1,249 @@
+use chrono::Datelike as _;
+
+/// Days without modification after which an open task is stale.
+pub const STALE_DAYS: i64 = 30;
+
+/// Steps of the weekly review, in order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Step {
+    Inbox,
+    Stale,
+    Projects,
+    Deferred,
+    Completed,
+}
+
+impl Step {
+    pub const ALL: [Self; 5] = [
+        Self::Inbox,
+        Self::Stale,
+        Self::Projects,
+        Self::Deferred,
+        Self::Completed,
+    ];
+
+    pub fn index(&self) -> usize {
+        Self::ALL.iter().position(|x| x == self).unwrap_or_default()
+    }
+
+    pub fn next(&self) -> Option<Self> {
+        Self::ALL.get(self.index() + 1).copied()
+    }
+
+    pub fn previous(&self) -> Option<Self> {
+        Self::ALL.get(self.index().checked_sub(1)?).copied()
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Inbox => "Empty the inbox",
+            Self::Stale => "Stale tasks",
+            Self::Projects => "Projects without next action",
+            Self::Deferred => "Deferred until this week",
+            Self::Completed => "Recently completed",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Inbox => "Give each task a project, or complete, defer or delete it.",
+            Self::Stale => {
+                "Open for more than a month without due or defer date: plan, flag or drop them."
+            }
+            Self::Projects => {
+                "Active projects without actionable task: add the next action or mark them done."
+            }
+            Self::Deferred => "These tasks become available this week.",
+            Self::Completed => {
+                "Done during the last seven days, finishing marks projects reviewed."
+            }
+        }
+    }
+}
+
+/// An open task created or last modified long ago.
+pub fn is_stale(task: &crate::tasks::Task, today: chrono::NaiveDate) -> bool {
+    !task.finished && task.age(today).is_some_and(|age| age >= STALE_DAYS)
+}
+
+/// Stale tasks without due or defer date.
+pub fn stale(tasks: &[crate::tasks::Task], today: chrono::NaiveDate) -> Vec<crate::tasks::Task> {
+    tasks
+        .iter()
+        .filter(|x| is_stale(x, today) && x.due_date.is_none() && x.threshold_date.is_none())
+        .cloned()
+        .collect()
+}
+
+/// Active projects, with open tasks or explicitly marked active, without any
+/// task doable today.
+pub fn stalled_projects(
+    tasks: &[crate::tasks::Task],
+    projects: &crate::tasks::project::Projects,
+    today: chrono::NaiveDate,
+) -> Vec<String> {
+    use crate::tasks::project::Status;
+
+    let open = tasks.iter().filter(|x| !x.finished).collect::<Vec<_>>();
+
+    let mut names = open
+        .iter()
+        .flat_map(|x| x.projects.iter())
+        .chain(
+            projects
+                .iter()
+                .filter(|(_, metadata)| metadata.status == Status::Active)
+                .map(|(name, _)| name),
+        )
+        .cloned()
+        .collect::<std::collections::BTreeSet<_>>();
+
+    names.retain(|name| {
+        projects
+            .get(name)
+            .is_none_or(|x| x.status == Status::Active)
+            && !open.iter().any(|x| {
+                x.projects.contains(name) && x.threshold_date.is_none_or(|date| date <= today)
+            })
+    });
+
+    names.into_iter().collect()
+}
+
+/// Open tasks whose defer date falls during the current week.
+pub fn deferred(tasks: &[crate::tasks::Task], today: chrono::NaiveDate) -> Vec<crate::tasks::Task> {
+    let monday = today - chrono::Duration::days(today.weekday().num_days_from_monday().into());
+    let next_monday = monday + chrono::Duration::weeks(1);
+
+    tasks
+        .iter()
+        .filter(|x| {
+            !x.finished
+                && x.threshold_date
+                    .is_some_and(|date| date >= monday && date < next_monday)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Tasks completed during the last seven days.
+pub fn completed(
+    tasks: &[crate::tasks::Task],
+    today: chrono::NaiveDate,
+) -> Vec<crate::tasks::Task> {
+    let limit = today - chrono::Duration::days(7);
+
+    tasks
+        .iter()
+        .filter(|x| x.finished && x.finish_date.is_some_and(|date| date > limit))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    fn tasks(lines: &[&str]) -> Vec<crate::tasks::Task> {
+        lines
+            .iter()
+            .map(|x| crate::tasks::Task::from(x.to_string()))
+            .collect()
+    }
+
+    fn subjects(tasks: &[crate::tasks::Task]) -> Vec<String> {
+        tasks.iter().map(|x| x.subject.clone()).collect()
+    }
+
+    /// A Wednesday.
+    fn today() -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(2042, 1, 15).unwrap()
+    }
+
+    fn review() -> Vec<crate::tasks::Task> {
+        tasks(&[
+            "2041-12-01 Old idea",
+            "2041-12-01 Old but modified modified:2042-01-10",
+            "2041-12-01 Old but due due:2042-02-01",
+            "2042-01-10 Recent idea",
+            "Write +book t:2042-01-17",
+            "Call +house @phone",
+            "x 2042-01-14 2042-01-01 Paint +garden",
+            "x 2041-12-14 2041-12-01 Cut +hedge",
+        ])
+    }
+
+    fn projects() -> crate::tasks::project::Projects {
+        let mut projects = crate::tasks::project::Projects::new();
+        projects.insert("garden".to_string(), Default::default());
+        projects.insert(
+            "house".to_string(),
+            crate::tasks::project::Metadata {
+                status: crate::tasks::project::Status::Someday,
+                ..Default::default()
+            },
+        );
+
+        projects
+    }
+
+    #[test]
+    fn is_stale() {
+        let is_stale = |line: &str| super::is_stale(&tasks(&[line])[0], today());
+
+        assert!(is_stale("2041-12-01 Old idea"));
+        assert!(!is_stale("2041-12-01 Old idea modified:2042-01-10"));
+        assert!(!is_stale("x 2041-12-02 2041-12-01 Old idea"));
+        assert!(!is_stale("Undated idea"));
+        assert!(!is_stale("x 2042-01-14 2042-01-20 Print"));
+    }
+
+    #[test]
+    fn stale() {
+        assert_eq!(subjects(&super::stale(&review(), today())), ["Old idea"]);
+        assert!(super::stale(&[], today()).is_empty());
+    }
+
+    #[test]
+    fn deferred() {
+        assert_eq!(
+            subjects(&super::deferred(&review(), today())),
+            ["Write +book"]
+        );
+        assert!(super::deferred(&[], today()).is_empty());
+    }
+
+    #[test]
+    fn completed() {
+        assert_eq!(
+            subjects(&super::completed(&review(), today())),
+            ["Paint +garden"]
+        );
+        assert!(super::completed(&tasks(&["x Undated done"]), today()).is_empty());
+        assert_eq!(
+            subjects(&super::completed(
+                &tasks(&["x 2042-01-14 2042-01-20 Print"]),
+                today()
+            )),
+            ["Print"]
+        );
+    }
+
+    #[test]
+    fn stalled_projects() {
+        assert_eq!(
+            super::stalled_projects(&review(), &projects(), today()),
+            ["book", "garden"]
+        );
+        assert!(super::stalled_projects(&[], &Default::default(), today()).is_empty());
+        assert_eq!(
+            super::stalled_projects(&[], &projects(), today()),
+            ["garden"]
+        );
+    }
+
+    #[test]
+    fn steps() {
+        assert_eq!(super::Step::Inbox.previous(), None);
+        assert_eq!(super::Step::Inbox.next(), Some(super::Step::Stale));
+        assert_eq!(super::Step::Completed.next(), None);
+    }
+}
@@ -0,0 +1,163 @@
+/// Open and done tasks at the end of a day.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    pub date: chrono::NaiveDate,
+    pub open: usize,
+    pub done: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub total: usize,
+    pub done: usize,
+    pub overdue: usize,
+    /// Average days between creation and completion.
+    pub cycle_time: Option<f64>,
+    /// One point per day, from the first creation date to `today`.
+    pub burndown: Vec<Point>,
+}
+
+impl Stats {
+    pub fn new(tasks: &[crate::tasks::Task], today: chrono::NaiveDate) -> Self {
+        let done = tasks.iter().filter(|x| x.finished).count();
+        let overdue = tasks
+            .iter()
+            .filter(|x| !x.finished && x.due_date.is_some_and(|due| due < today))
+            .count();
+
+        let cycles = tasks
+            .iter()
+            .filter_map(|x| Some((x.finish_date? - x.create_date?).num_days()))
+            // Ignores tasks finished before their creation
+            .filter(|x| *x >= 0)
+            .collect::<Vec<_>>();
+        let cycle_time = if cycles.is_empty() {
+            None
+        } else {
+            Some(cycles.iter().sum::<i64>() as f64 / cycles.len() as f64)
+        };
+
+        Self {
+            total: tasks.len(),
+            done,
+            overdue,
+            cycle_time,
+            burndown: burndown(tasks, today),
+        }
+    }
+
+    /// Human readable summary.
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{}/{} done", self.done, self.total);
+
+        if let Some(percent) = (self.done * 100).checked_div(self.total) {
+            summary.push_str(&format!(" ({percent}%)"));
+        }
+
+        summary.push_str(&format!(" · {} overdue", self.overdue));
+
+        if let Some(cycle_time) = self.cycle_time {
+            summary.push_str(&format!(" · {cycle_time:.1} days to complete on average"));
+        }
+
+        summary
+    }
+}
+
+/// Tasks without creation date are counted from the start.
+fn burndown(tasks: &[crate::tasks::Task], today: chrono::NaiveDate) -> Vec<Point> {
+    let Some(start) = tasks
+        .iter()
+        .filter_map(|x| x.create_date.or(x.finish_date))
+        .min()
+        .filter(|x| *x <= today)
+    else {
+        return Vec::new();
+    };
+
+    start
+        .iter_days()
+        .take_while(|x| *x <= today)
+        .map(|date| {
+            let created = tasks
+                .iter()
+                .filter(|x| x.create_date.is_none_or(|created| created <= date))
+                .collect::<Vec<_>>();
+            let done = created
+                .iter()
+                .filter(|x| x.finished && x.finish_date.is_none_or(|finished| finished <= date))
+                .count();
+
+            Point {
+                date,
+                open: created.len() - done,
+                done,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    fn stats(lines: &[&str]) -> super::Stats {
+        let tasks = lines
+            .iter()
+            .map(|x| crate::tasks::Task::from(x.to_string()))
+            .collect::<Vec<_>>();
+
+        super::Stats::new(
+            &tasks,
+            chrono::NaiveDate::from_ymd_opt(2042, 1, 15).unwrap(),
+        )
+    }
+
+    const BOOK: [&str; 4] = [
+        "x 2042-01-14 2042-01-12 Write +book",
+        "2042-01-13 Review +book due:2042-01-14",
+        "2042-01-13 Publish +book due:2042-01-20",
+        "x 2042-01-15 2042-01-13 Print +book",
+    ];
+
+    #[test]
+    fn new() {
+        let book = stats(&BOOK);
+
+        assert_eq!((book.total, book.done, book.overdue), (4, 2, 1));
+        assert_eq!(book.cycle_time, Some(2.));
+        assert_eq!(stats(&[]), super::Stats::default());
+        assert_eq!(stats(&["Write", "x Print"]).cycle_time, None);
+        assert_eq!(stats(&["x 2042-01-12 2042-01-14 Print"]).cycle_time, None);
+    }
+
+    #[test]
+    fn summary() {
+        assert_eq!(
+            stats(&BOOK).summary(),
+            "2/4 done (50%) · 1 overdue · 2.0 days to complete on average"
+        );
+        assert_eq!(stats(&[]).summary(), "0/0 done · 0 overdue");
+    }
+
+    #[test]
+    fn burndown() {
+        let burndown = |lines: &[&str]| {
+            stats(lines)
+                .burndown
+                .iter()
+                .map(|x| (x.open, x.done))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            stats(&BOOK).burndown[0].date,
+            chrono::NaiveDate::from_ymd_opt(2042, 1, 12).unwrap()
+        );
+        assert_eq!(burndown(&BOOK), [(1, 0), (4, 0), (3, 1), (2, 2)]);
+        assert!(burndown(&["Write", "x Print"]).is_empty());
+        assert_eq!(burndown(&["Write", "2042-01-14 Read"]), [(2, 0), (2, 0)]);
+        assert_eq!(
+            burndown(&["x 2042-01-12 2042-01-14 Print"]),
+            [(0, 1), (0, 1)]
+        );
+    }
+}
@@ -1,3 +1,12 @@
+pub const TIME_TAG: &str = "at";
+pub const DURATION_TAG: &str = "dur";
+pub const REMIND_TAG: &str = "remind";
+pub const PLAN_TAG: &str = "plan";
+pub const SPENT_TAG: &str = "spent";
+pub const ESTIMATE_TAG: &str = "est";
+pub const MODIFIED_TAG: &str = "modified";
+pub const UID_TAG: &str = "uid";
+
 #[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Task {
     inner: todo_txt::task::Extended,
@@ -36,6 +45,177 @@ impl Task {
     fn markup_escape(text: &str) -> String {
         gtk::glib::markup_escape_text(text).as_str().to_string()
     }
+
+    /// Tags edited by a dedicated widget rather than as free keywords.
+    pub const SPECIAL_TAGS: &'static [&'static str] = &[
+        TIME_TAG,
+        DURATION_TAG,
+        REMIND_TAG,
+        PLAN_TAG,
+        ESTIMATE_TAG,
+        MODIFIED_TAG,
+        UID_TAG,
+    ];
+
+    pub fn keywords(&self) -> std::collections::BTreeMap<String, String> {
+        self.tags
+            .iter()
+            .filter(|(k, _)| !Self::SPECIAL_TAGS.contains(&k.as_str()))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    pub fn due_time(&self) -> Option<chrono::NaiveTime> {
+        self.tags
+            .get(TIME_TAG)
+            .and_then(|x| crate::date::parse_time(x))
+    }
+
+    pub fn set_due_time(&mut self, time: Option<chrono::NaiveTime>) {
+        self.set_tag(TIME_TAG, time.map(crate::date::format_time));
+    }
+
+    pub fn due_datetime(&self) -> Option<chrono::NaiveDateTime> {
+        self.due_date
+            .map(|x| x.and_time(self.due_time().unwrap_or_default()))
+    }
+
+    /// A reminder is either a full date time (`remind:2042-01-01T09:00`) or a
+    /// time of day on the due date (`remind:09:00`).
+    pub fn remind_at(&self) -> Option<chrono::NaiveDateTime> {
+        let remind = self.tags.get(REMIND_TAG)?;
+
+        if let Ok(datetime) = chrono::NaiveDateTime::parse_from_str(remind, "%Y-%m-%dT%H:%M") {
+            return Some(datetime);
+        }
+
+        let time = crate::date::parse_time(remind)?;
+        let date = self.due_date.unwrap_or_else(crate::date::today);
+
+        Some(date.and_time(time))
+    }
+
+    pub fn remind(&self) -> Option<&String> {
+        self.tags.get(REMIND_TAG)
+    }
+
+    pub fn set_remind(&mut self, remind: Option<String>) {
+        self.set_tag(REMIND_TAG, remind);
+    }
+
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        self.tags
+            .get(DURATION_TAG)
+            .and_then(|x| crate::date::parse_duration(x))
+    }
+
+    pub fn set_duration(&mut self, duration: Option<chrono::Duration>) {
+        self.set_tag(DURATION_TAG, duration.map(crate::date::format_duration));
+    }
+
+    pub fn modified(&self) -> Option<chrono::NaiveDate> {
+        self.tags
+            .get(MODIFIED_TAG)
+            .and_then(|x| chrono::NaiveDate::parse_from_str(x, "%Y-%m-%d").ok())
+    }
+
+    /// Records the last modification date, for changes made by the user to
+    /// the task itself: editing, completing, attaching files or tracking time.
+    /// Planning it or renaming its tags isn't a modification.
+    pub fn touch(&mut self, date: chrono::NaiveDate) {
+        self.set_tag(MODIFIED_TAG, Some(date.format("%Y-%m-%d").to_string()));
+    }
+
+    /// Days since the task was created or last modified, 0 for dates in the
+    /// future.
+    pub fn age(&self, today: chrono::NaiveDate) -> Option<i64> {
+        let last = self.modified().max(self.create_date)?;
+
+        Some((today - last).num_days().max(0))
+    }
+
+    /// Estimated work, unlike the duration which is the time blocked in the
+    /// agenda.
+    pub fn estimate(&self) -> Option<chrono::Duration> {
+        self.tags
+            .get(ESTIMATE_TAG)
+            .and_then(|x| crate::date::parse_duration(x))
+    }
+
+    pub fn set_estimate(&mut self, estimate: Option<chrono::Duration>) {
+        self.set_tag(ESTIMATE_TAG, estimate.map(crate::date::format_duration));
+    }
+
+    /// Time tracked on the task.
+    pub fn spent(&self) -> Option<chrono::Duration> {
+        self.tags
+            .get(SPENT_TAG)
+            .and_then(|x| crate::date::parse_duration(x))
+    }
+
+    pub fn add_spent(&mut self, duration: chrono::Duration) {
+        let Some(spent) = self.spent().unwrap_or_default().checked_add(&duration) else {
+            log::warn!("Time spent on “{}” out of range", self.subject);
+            return;
+        };
+
+        self.set_tag(SPENT_TAG, Some(crate::date::format_duration(spent)));
+    }
+
+    /// Identifier of the task in exported calendars, kept across edits.
+    pub fn uid(&self) -> Option<&str> {
+        self.tags.get(UID_TAG).map(String::as_str)
+    }
+
+    pub fn set_uid(&mut self, uid: &str) {
+        self.set_tag(UID_TAG, Some(uid.to_string()));
+    }
+
+    /// Day the task is planned for and its rank in that day plan, stored as
+    /// `plan:2042-01-01/2`.
+    pub fn plan(&self) -> Option<(chrono::NaiveDate, usize)> {
+        let (date, rank) = self.tags.get(PLAN_TAG)?.split_once('/')?;
+
+        Some((
+            chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?,
+            rank.parse().ok()?,
+        ))
+    }
+
+    pub fn set_plan(&mut self, plan: Option<(chrono::NaiveDate, usize)>) {
+        self.set_tag(
+            PLAN_TAG,
+            plan.map(|(date, rank)| format!("{}/{rank}", date.format("%Y-%m-%d"))),
+        );
+    }
+
+    /// Next occurrence of a recurrent task, once completed.
+    pub fn next_occurrence(&self) -> Option<Self> {
+        let recurrence = self.recurrence.as_ref()?;
+
+        let due = match self.due_date {
+            Some(due_date) if recurrence.strict => due_date,
+            _ => crate::date::today(),
+        };
+
+        let mut new = self.clone();
+        new.uncomplete();
+        new.create_date = Some(crate::date::today());
+        new.due_date = Some(recurrence.clone() + due);
+
+        if let Some(threshold_date) = self.threshold_date {
+            new.threshold_date = Some(recurrence.clone() + threshold_date);
+        }
+
+        Some(new)
+    }
+
+    fn set_tag(&mut self, name: &str, value: Option<String>) {
+        match value {
+            Some(value) => self.tags.insert(name.to_string(), value),
+            None => self.tags.remove(name),
+        };
+    }
 }
 
 impl todo_txt::Task for Task {}
@@ -102,4 +282,69 @@ mod tests {
             "P&amp;T keep focus on long term <b>+HoWE</b>"
         );
     }
+
+    #[test]
+    fn time_tags() {
+        use std::str::FromStr as _;
+
+        let mut task = Task::from_str("Meeting due:2042-01-01 at:14:30 dur:45m").unwrap();
+
+        assert_eq!(task.due_time(), chrono::NaiveTime::from_hms_opt(14, 30, 0));
+        assert_eq!(task.duration(), Some(chrono::Duration::minutes(45)));
+        assert!(task.keywords().is_empty());
+
+        task.set_due_time(None);
+        task.set_duration(Some(chrono::Duration::minutes(90)));
+
+        assert_eq!(task.to_string(), "Meeting due:2042-01-01 dur:1h30m");
+    }
+
+    #[test]
+    fn age() {
+        let date = |day| chrono::NaiveDate::from_ymd_opt(2042, 1, day).unwrap();
+        let mut task = Task::from("Meeting".to_string());
+
+        assert_eq!(task.age(date(31)), None);
+        task.create_date = Some(date(1));
+        assert_eq!(task.age(date(31)), Some(30));
+        task.touch(date(21));
+        assert_eq!(task.age(date(31)), Some(10));
+        assert_eq!(task.to_string(), "2042-01-01 Meeting modified:2042-01-21");
+        assert!(task.keywords().is_empty());
+
+        let task = Task::from("Meeting modified:2042-02-10".to_string());
+        assert_eq!(task.age(date(31)), Some(0));
+    }
+
+    #[test]
+    fn add_spent() {
+        let mut task = Task::from("Meeting".to_string());
+
+        task.add_spent(chrono::Duration::minutes(25));
+        task.add_spent(chrono::Duration::minutes(50));
+        assert_eq!(task.spent(), Some(chrono::Duration::minutes(75)));
+        assert_eq!(task.keywords().get("spent"), Some(&"1h15m".to_string()));
+    }
+
+    #[test]
+    fn remind_at() {
+        let datetime = |day, hour, minute| {
+            chrono::NaiveDate::from_ymd_opt(2042, 1, day)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+        };
+        let remind_at = |line: &str| Task::from(line.to_string()).remind_at();
+
+        assert_eq!(remind_at("Call remind:2042-01-01T09:00"), datetime(1, 9, 0));
+        assert_eq!(
+            remind_at("Call due:2042-01-02 remind:2042-01-01T09:00"),
+            datetime(1, 9, 0)
+        );
+        assert_eq!(
+            remind_at("Call due:2042-01-02 remind:09:30"),
+            datetime(2, 9, 30)
+        );
+        assert_eq!(remind_at("Call due:2042-01-02 remind:later"), None);
+        assert_eq!(remind_at("Call due:2042-01-02"), None);
+    }
 }
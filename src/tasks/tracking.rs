@@ -0,0 +1,162 @@
+/// Time spent on a task during a day, one line of the time log like
+/// `2042-01-01 25m (A) 2041-12-30 Write +book @desk`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub date: chrono::NaiveDate,
+    pub duration: chrono::Duration,
+    /// The task line when the time was tracked.
+    pub task: String,
+}
+
+impl std::str::FromStr for Entry {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid time log line {line:?}");
+
+        let mut fields = line.splitn(3, ' ');
+        let date = fields
+            .next()
+            .and_then(|x| chrono::NaiveDate::parse_from_str(x, "%Y-%m-%d").ok())
+            .ok_or_else(invalid)?;
+        let duration = fields
+            .next()
+            .and_then(crate::date::parse_duration)
+            .ok_or_else(invalid)?;
+        let task = fields.next().unwrap_or_default().trim().to_string();
+
+        Ok(Self {
+            date,
+            duration,
+            task,
+        })
+    }
+}
+
+impl std::fmt::Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.date.format("%Y-%m-%d"),
+            crate::date::format_duration(self.duration),
+            self.task
+        )
+    }
+}
+
+/// Time spent by project and context, the largest first.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    pub total: chrono::Duration,
+    pub projects: Vec<(String, chrono::Duration)>,
+    pub contexts: Vec<(String, chrono::Duration)>,
+}
+
+impl Report {
+    /// Entries between `from` and `to`, both included.
+    pub fn new(entries: &[Entry], from: chrono::NaiveDate, to: chrono::NaiveDate) -> Self {
+        let entries = entries
+            .iter()
+            .filter(|x| x.date >= from && x.date <= to)
+            .map(|x| (crate::tasks::Task::from(x.task.clone()), x.duration))
+            .collect::<Vec<_>>();
+
+        Self {
+            total: entries.iter().map(|(_, duration)| *duration).sum(),
+            projects: sum(&entries, |x| &x.projects),
+            contexts: sum(&entries, |x| &x.contexts),
+        }
+    }
+}
+
+fn sum<F: Fn(&crate::tasks::Task) -> &Vec<String>>(
+    entries: &[(crate::tasks::Task, chrono::Duration)],
+    keys: F,
+) -> Vec<(String, chrono::Duration)> {
+    let mut sums = std::collections::BTreeMap::<String, chrono::Duration>::new();
+
+    for (task, duration) in entries {
+        for key in keys(task) {
+            *sums.entry(key.clone()).or_default() += *duration;
+        }
+    }
+
+    let mut sums = sums.into_iter().collect::<Vec<_>>();
+    sums.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    sums
+}
+
+/// The time log, `$EFFITASK_TIME_FILE` or `$TODO_DIR/time.txt`.
+pub fn path() -> std::path::PathBuf {
+    match std::env::var("EFFITASK_TIME_FILE") {
+        Ok(path) => path.into(),
+        Err(_) => {
+            std::path::Path::new(&std::env::var("TODO_DIR").unwrap_or_default()).join("time.txt")
+        }
+    }
+}
+
+pub fn load() -> Vec<Entry> {
+    let Ok(contents) = std::fs::read_to_string(path()) else {
+        return Vec::new();
+    };
+
+    contents
+        .lines()
+        .filter(|x| !x.trim().is_empty())
+        .filter_map(|x| x.parse().inspect_err(|err| log::warn!("{err}")).ok())
+        .collect()
+}
+
+pub fn append(entry: &Entry) -> Result<(), String> {
+    use std::io::Write as _;
+
+    let path = path();
+
+    std::fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{entry}"))
+        .map_err(|err| format!("Unable to write {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn report() {
+        let date = |day| chrono::NaiveDate::from_ymd_opt(2042, 1, day).unwrap();
+        let entries = [
+            "2042-01-01 25m Write +book @desk",
+            "2042-01-02 1h Review +book +blog @desk",
+            "2042-01-03 30m (A) 2042-01-01 Call @phone spent:1h",
+            "2042-01-04 2h Write +book @desk",
+        ]
+        .map(|x| x.parse::<super::Entry>().unwrap());
+
+        assert_eq!(
+            entries[1].to_string(),
+            "2042-01-02 1h Review +book +blog @desk"
+        );
+        assert!("2042-01-01 Write".parse::<super::Entry>().is_err());
+
+        let report = super::Report::new(&entries, date(1), date(3));
+        let minutes = |x: &[(String, chrono::Duration)]| {
+            x.iter()
+                .map(|(name, duration)| (name.clone(), duration.num_minutes()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(report.total, chrono::Duration::minutes(115));
+        assert_eq!(
+            minutes(&report.projects),
+            vec![("book".to_string(), 85), ("blog".to_string(), 60)]
+        );
+        assert_eq!(
+            minutes(&report.contexts),
+            vec![("desk".to_string(), 85), ("phone".to_string(), 30)]
+        );
+    }
+}
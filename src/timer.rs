@@ -0,0 +1,191 @@
+use adw::prelude::*;
+
+const WORK: chrono::Duration = chrono::Duration::minutes(25);
+const BREAK: chrono::Duration = chrono::Duration::minutes(5);
+const LONG_BREAK: chrono::Duration = chrono::Duration::minutes(15);
+/// Pomodoros before a long break.
+const LONG_BREAK_EVERY: usize = 4;
+
+#[derive(Debug)]
+pub enum Msg {
+    /// Keeps timing the task after it was edited from the old to the new one.
+    Follow(Box<crate::tasks::Task>, Box<crate::tasks::Task>),
+    Start(Box<crate::tasks::Task>),
+    Stop,
+    Tick,
+}
+
+#[derive(Debug)]
+pub enum MsgOutput {
+    /// Time spent working on the task.
+    Track(Box<crate::tasks::Task>, chrono::Duration),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Phase {
+    Work,
+    Break,
+}
+
+pub struct Model {
+    task: Option<crate::tasks::Task>,
+    phase: Phase,
+    started: chrono::NaiveDateTime,
+    pomodoros: usize,
+}
+
+impl Model {
+    fn duration(&self) -> chrono::Duration {
+        match self.phase {
+            Phase::Work => WORK,
+            Phase::Break if self.pomodoros.is_multiple_of(LONG_BREAK_EVERY) => LONG_BREAK,
+            Phase::Break => BREAK,
+        }
+    }
+
+    fn label(&self) -> String {
+        let Some(task) = &self.task else {
+            return String::new();
+        };
+
+        let remaining =
+            (self.duration() - (crate::date::now() - self.started)).max(chrono::Duration::zero());
+        let phase = match self.phase {
+            Phase::Work => "🍅",
+            Phase::Break => "☕",
+        };
+
+        format!(
+            "{phase} {:02}:{:02} {}",
+            remaining.num_minutes(),
+            remaining.num_seconds() % 60,
+            task.subject
+        )
+    }
+
+    fn start(&mut self, sender: &relm4::ComponentSender<Self>, task: crate::tasks::Task) {
+        self.stop(sender);
+
+        self.task = Some(task);
+        self.phase = Phase::Work;
+        self.started = crate::date::now();
+        self.pomodoros = 0;
+    }
+
+    fn follow(&mut self, old: &crate::tasks::Task, new: &crate::tasks::Task) {
+        if let Some(task) = &mut self.task
+            && task.to_string() == old.to_string()
+        {
+            *task = new.clone();
+        }
+    }
+
+    /// Tracks the time worked since the pomodoro start.
+    fn stop(&mut self, sender: &relm4::ComponentSender<Self>) {
+        if let Some(task) = self.task.take()
+            && self.phase == Phase::Work
+        {
+            let spent = crate::date::now() - self.started;
+
+            if spent >= chrono::Duration::minutes(1) {
+                sender.output(MsgOutput::Track(Box::new(task), spent)).ok();
+            }
+        }
+    }
+
+    fn tick(&mut self, sender: &relm4::ComponentSender<Self>) {
+        let Some(task) = &self.task else {
+            return;
+        };
+
+        if crate::date::now() - self.started < self.duration() {
+            return;
+        }
+
+        match self.phase {
+            Phase::Work => {
+                self.pomodoros += 1;
+                self.phase = Phase::Break;
+                sender
+                    .output(MsgOutput::Track(Box::new(task.clone()), WORK))
+                    .ok();
+                Self::notify(
+                    "Pomodoro done",
+                    &format!("Take a break from “{}”", task.subject),
+                );
+            }
+            Phase::Break => {
+                self.phase = Phase::Work;
+                Self::notify("Break over", &format!("Back to “{}”", task.subject));
+            }
+        }
+
+        self.started = crate::date::now();
+    }
+
+    fn notify(title: &str, body: &str) {
+        let notification = gtk::gio::Notification::new(title);
+        notification.set_body(Some(body));
+
+        relm4::main_application().send_notification(Some("pomodoro"), &notification);
+    }
+}
+
+#[relm4::component(pub)]
+impl relm4::Component for Model {
+    type CommandOutput = ();
+    type Init = ();
+    type Input = Msg;
+    type Output = MsgOutput;
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        let model = Self {
+            task: None,
+            phase: Phase::Work,
+            started: crate::date::now(),
+            pomodoros: 0,
+        };
+
+        let widgets = view_output!();
+
+        gtk::glib::timeout_add_seconds_local(1, move || {
+            sender.input(Msg::Tick);
+            gtk::glib::ControlFlow::Continue
+        });
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>, _: &Self::Root) {
+        match msg {
+            Msg::Follow(old, new) => self.follow(&old, &new),
+            Msg::Start(task) => self.start(&sender, *task),
+            Msg::Stop => self.stop(&sender),
+            Msg::Tick => self.tick(&sender),
+        }
+    }
+
+    view! {
+        gtk::Box {
+            set_spacing: 5,
+            #[watch]
+            set_visible: model.task.is_some(),
+
+            gtk::Label {
+                set_ellipsize: gtk::pango::EllipsizeMode::End,
+                set_max_width_chars: 30,
+                #[watch]
+                set_text: &model.label(),
+            },
+            gtk::Button {
+                set_icon_name: "media-playback-stop",
+                set_tooltip_text: Some("Stop the timer and track the time spent"),
+                connect_clicked => Msg::Stop,
+            },
+        }
+    }
+}
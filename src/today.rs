@@ -0,0 +1,136 @@
+use adw::prelude::*;
+
+#[derive(Debug)]
+pub enum Msg {
+    Clear,
+    RollOver,
+    Update,
+}
+
+pub struct Model {
+    tasks: relm4::Controller<crate::widgets::tasks::Model>,
+}
+
+impl Model {
+    /// Tasks planned for today, and the unfinished ones of the previous days.
+    pub fn tasks() -> Vec<crate::tasks::Task> {
+        let today = crate::date::today();
+        let list = crate::application::tasks();
+        let preferences = crate::application::preferences();
+
+        list.tasks
+            .iter()
+            .filter(|x| {
+                x.plan()
+                    .is_some_and(|(date, _)| date == today || (date < today && !x.finished))
+                    && (preferences.done || !x.finished)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Plans the unfinished tasks for `date`, or removes them from the plan.
+    fn move_unfinished(
+        &self,
+        sender: &relm4::ComponentSender<Self>,
+        date: Option<chrono::NaiveDate>,
+    ) {
+        let mut tasks = Self::tasks()
+            .into_iter()
+            .filter(|x| !x.finished)
+            .collect::<Vec<_>>();
+        tasks.sort_by_key(|x| x.plan());
+
+        let ids = tasks.iter().map(|x| x.id).collect::<Vec<_>>();
+
+        if !ids.is_empty() {
+            sender
+                .output(crate::widgets::task::MsgOutput::Bulk(
+                    ids,
+                    crate::tasks::bulk::Action::Plan(date),
+                ))
+                .ok();
+        }
+    }
+}
+
+#[relm4::component(pub)]
+impl relm4::SimpleComponent for Model {
+    type Init = ();
+    type Input = Msg;
+    type Output = crate::widgets::task::MsgOutput;
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        use relm4::Component as _;
+        use relm4::ComponentController as _;
+
+        let tasks = crate::widgets::tasks::Model::builder()
+            .launch(crate::Filter::from(Model::tasks))
+            .forward(sender.output_sender(), std::convert::identity);
+        tasks.emit(crate::widgets::tasks::MsgInput::SortBy(|a, b| {
+            a.plan().cmp(&b.plan())
+        }));
+        tasks.emit(crate::widgets::tasks::MsgInput::Reorderable(
+            crate::date::today(),
+        ));
+
+        let model = Self { tasks };
+
+        let widgets = view_output!();
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
+        use relm4::ComponentController as _;
+
+        let today = crate::date::today();
+
+        match msg {
+            Msg::Clear => self.move_unfinished(&sender, None),
+            Msg::RollOver => self.move_unfinished(&sender, today.succ_opt()),
+            Msg::Update => {
+                self.tasks
+                    .emit(crate::widgets::tasks::MsgInput::Reorderable(today));
+                self.tasks.emit(crate::widgets::tasks::MsgInput::NeedUpdate);
+            }
+        }
+    }
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+
+            gtk::Box {
+                set_margin_end: 10,
+                set_margin_start: 10,
+                set_margin_top: 5,
+                set_orientation: gtk::Orientation::Horizontal,
+                set_spacing: 5,
+
+                gtk::Label {
+                    add_css_class: "dim-label",
+                    set_hexpand: true,
+                    set_text: "Press P on a task to plan it for today, drag tasks to reorder them",
+                    set_wrap: true,
+                    set_xalign: 0.,
+                },
+                gtk::Button {
+                    set_label: "Roll over",
+                    set_tooltip_text: Some("Plan the unfinished tasks for tomorrow"),
+                    connect_clicked => Msg::RollOver,
+                },
+                gtk::Button {
+                    set_label: "Clear",
+                    set_tooltip_text: Some("Remove the unfinished tasks from the plan"),
+                    connect_clicked => Msg::Clear,
+                },
+            },
+            append: model.tasks.widget(),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use adw::prelude::*;
+
+#[derive(Debug)]
+pub enum MsgInput {
+    Update(Vec<crate::tasks::stats::Point>),
+}
+
+pub struct Model {
+    points: std::rc::Rc<std::cell::RefCell<Vec<crate::tasks::stats::Point>>>,
+}
+
+impl Model {
+    /// Draws the open tasks in red and the done ones in green, day by day.
+    fn draw(
+        points: &[crate::tasks::stats::Point],
+        context: &gtk::cairo::Context,
+        width: f64,
+        height: f64,
+    ) -> Result<(), gtk::cairo::Error> {
+        use super::chart::{GREEN, RED};
+
+        let labels = points
+            .iter()
+            .map(|x| x.date.format("%Y-%m-%d").to_string())
+            .collect::<Vec<_>>();
+        let open = points.iter().map(|x| x.open).collect();
+        let done = points.iter().map(|x| x.done).collect();
+
+        super::chart::lines(
+            context,
+            width,
+            height,
+            &labels,
+            &[(open, RED), (done, GREEN)],
+        )
+    }
+}
+
+#[relm4::component(pub)]
+impl relm4::Component for Model {
+    type CommandOutput = ();
+    type Init = ();
+    type Input = MsgInput;
+    type Output = ();
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        _sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        let model = Self {
+            points: Default::default(),
+        };
+
+        let points = model.points.clone();
+        root.set_draw_func(move |_, context, width, height| {
+            Self::draw(&points.borrow(), context, width.into(), height.into()).ok();
+        });
+
+        let widgets = view_output!();
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        _: &mut Self::Widgets,
+        msg: Self::Input,
+        _: relm4::ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match msg {
+            MsgInput::Update(points) => {
+                *self.points.borrow_mut() = points;
+                root.queue_draw();
+            }
+        }
+    }
+
+    view! {
+        gtk::DrawingArea {
+            set_content_height: 200,
+            set_content_width: 400,
+        }
+    }
+}
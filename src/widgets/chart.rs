@@ -0,0 +1,146 @@
+//! Small cairo charts.
+
+pub type Color = (f64, f64, f64);
+
+pub const BLUE: Color = (0.27, 0.6, 0.91);
+pub const GREEN: Color = (0.3, 0.7, 0.4);
+pub const RED: Color = (1., 0.4, 0.5);
+
+const MARGIN: f64 = 20.;
+const GRAY: Color = (0.5, 0.5, 0.5);
+
+/// Vertical bars, one group by label with a bar by serie.
+pub fn bars(
+    context: &gtk::cairo::Context,
+    width: f64,
+    height: f64,
+    labels: &[String],
+    series: &[(Vec<usize>, Color)],
+) -> Result<(), gtk::cairo::Error> {
+    if labels.is_empty() {
+        return Ok(());
+    }
+
+    let max = max(series.iter().flat_map(|(values, _)| values));
+    let group = (width - 2. * MARGIN) / labels.len() as f64;
+    let bar = group * 0.8 / series.len().max(1) as f64;
+    let dy = (height - 2. * MARGIN) / max as f64;
+
+    axes(context, width, height, max)?;
+
+    for (s, (values, color)) in series.iter().enumerate() {
+        set_color(context, *color);
+
+        for (n, value) in values.iter().enumerate() {
+            let x = MARGIN + n as f64 * group + group * 0.1 + s as f64 * bar;
+            let h = *value as f64 * dy;
+
+            context.rectangle(x, height - MARGIN - h, bar - 1., h);
+        }
+
+        context.fill()?;
+    }
+
+    set_color(context, GRAY);
+
+    for (n, label) in labels.iter().enumerate() {
+        context.move_to(MARGIN + n as f64 * group, height - 5.);
+        context.show_text(label)?;
+    }
+
+    Ok(())
+}
+
+/// A line by serie, with a point by label.
+pub fn lines(
+    context: &gtk::cairo::Context,
+    width: f64,
+    height: f64,
+    labels: &[String],
+    series: &[(Vec<usize>, Color)],
+) -> Result<(), gtk::cairo::Error> {
+    let (Some(first), Some(last)) = (labels.first(), labels.last()) else {
+        return Ok(());
+    };
+
+    let max = max(series.iter().flat_map(|(values, _)| values));
+    let dx = (width - 2. * MARGIN) / (labels.len().max(2) - 1) as f64;
+    let dy = (height - 2. * MARGIN) / max as f64;
+
+    axes(context, width, height, max)?;
+
+    context.move_to(MARGIN, height - 5.);
+    context.show_text(first)?;
+    context.move_to(width - MARGIN - 60., height - 5.);
+    context.show_text(last)?;
+
+    context.set_line_width(2.);
+
+    for (values, color) in series {
+        set_color(context, *color);
+
+        for (n, value) in values.iter().enumerate() {
+            context.line_to(MARGIN + n as f64 * dx, height - MARGIN - *value as f64 * dy);
+        }
+
+        context.stroke()?;
+    }
+
+    Ok(())
+}
+
+/// Horizontal bars, labelled with their name and value.
+pub fn hbars(
+    context: &gtk::cairo::Context,
+    width: f64,
+    height: f64,
+    entries: &[(String, usize)],
+    color: Color,
+) -> Result<(), gtk::cairo::Error> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let max = max(entries.iter().map(|(_, value)| value));
+    let row = height / entries.len() as f64;
+    let dx = (width - 2. * MARGIN) / max as f64;
+
+    for (n, (name, value)) in entries.iter().enumerate() {
+        let y = n as f64 * row;
+
+        set_color(context, color);
+        context.rectangle(MARGIN, y + 2., *value as f64 * dx, row - 4.);
+        context.fill()?;
+
+        set_color(context, (0., 0., 0.));
+        context.move_to(MARGIN + 4., y + row / 2. + 4.);
+        context.show_text(&format!("{name} ({value})"))?;
+    }
+
+    Ok(())
+}
+
+fn max<'a, I: IntoIterator<Item = &'a usize>>(values: I) -> usize {
+    values.into_iter().copied().max().unwrap_or(0).max(1)
+}
+
+fn axes(
+    context: &gtk::cairo::Context,
+    width: f64,
+    height: f64,
+    max: usize,
+) -> Result<(), gtk::cairo::Error> {
+    set_color(context, GRAY);
+    context.set_line_width(1.);
+    context.move_to(MARGIN, MARGIN);
+    context.line_to(MARGIN, height - MARGIN);
+    context.line_to(width - MARGIN, height - MARGIN);
+    context.stroke()?;
+
+    context.move_to(2., MARGIN);
+    context.show_text(&max.to_string())
+}
+
+fn set_color(context: &gtk::cairo::Context, (r, g, b): Color) {
+    context.set_source_rgb(r, g, b);
+}
@@ -3,6 +3,19 @@ use adw::prelude::*;
 pub struct Model {}
 
 impl Model {
+    /// Stale tasks fade until twice the staleness delay.
+    fn alpha(task: &crate::tasks::Task, today: chrono::NaiveDate) -> f64 {
+        use crate::tasks::review::STALE_DAYS;
+
+        if !crate::tasks::review::is_stale(task, today) {
+            return 1.;
+        }
+
+        let age = task.age(today).unwrap_or_default();
+
+        (1. - (age - STALE_DAYS) as f64 / (2 * STALE_DAYS) as f64).max(0.3)
+    }
+
     fn draw(
         task: &crate::tasks::Task,
         drawing_area: &gtk::DrawingArea,
@@ -13,16 +26,18 @@ impl Model {
             f64::from(drawing_area.height_request()) / 2.,
         );
 
+        let today = crate::date::today();
+        let alpha = Self::alpha(task, today);
+
         if task.finished || task.due_date.is_none() {
-            context.set_source_rgb(0.8, 0.8, 0.8);
+            context.set_source_rgba(0.8, 0.8, 0.8, alpha);
         } else {
             let due_date = task.due_date.unwrap();
-            let today = crate::date::today();
 
             if due_date < today {
-                context.set_source_rgb(1., 0.4, 0.5);
+                context.set_source_rgba(1., 0.4, 0.5, alpha);
             } else {
-                context.set_source_rgb(1., 0.8, 0.2);
+                context.set_source_rgba(1., 0.8, 0.2, alpha);
             }
         }
 
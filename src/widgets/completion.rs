@@ -0,0 +1,152 @@
+use adw::prelude::*;
+use gtk::glib;
+
+/// Shows a popover under `entry` completing the word being typed with
+/// `candidates`, when it starts with one of `triggers`. Without trigger, the
+/// entry holds a single word.
+///
+/// The candidates are computed on the first keystroke after the entry gets
+/// the focus, not on each one.
+pub fn attach<F: Fn() -> Vec<String> + 'static>(
+    entry: &gtk::Entry,
+    triggers: &'static str,
+    candidates: F,
+) {
+    let popover = gtk::Popover::new();
+    popover.set_autohide(false);
+    popover.set_can_focus(false);
+    popover.set_has_arrow(false);
+    popover.set_position(gtk::PositionType::Bottom);
+    popover.set_parent(entry);
+
+    let list_box = gtk::ListBox::new();
+    popover.set_child(Some(&list_box));
+
+    list_box.connect_row_activated(glib::clone!(
+        #[weak]
+        entry,
+        #[weak]
+        popover,
+        move |_, row| complete(&entry, &popover, row, !triggers.is_empty())
+    ));
+
+    let cache = std::rc::Rc::new(std::cell::RefCell::new(None::<Vec<String>>));
+
+    let refresh = glib::clone!(
+        #[weak]
+        popover,
+        #[weak]
+        list_box,
+        #[strong]
+        cache,
+        move |entry: &gtk::Entry| {
+            let suggestions = crate::tasks::completion::suggestions(
+                &entry.text(),
+                entry.position() as usize,
+                triggers,
+                cache.borrow_mut().get_or_insert_with(&candidates),
+            );
+
+            list_box.remove_all();
+
+            for suggestion in &suggestions {
+                let label = gtk::Label::new(Some(suggestion));
+                label.set_xalign(0.);
+                list_box.append(&label);
+            }
+
+            if suggestions.is_empty() {
+                popover.popdown();
+            } else {
+                list_box.select_row(list_box.row_at_index(0).as_ref());
+                popover.popup();
+            }
+        }
+    );
+    let refresh = std::rc::Rc::new(refresh);
+
+    entry.connect_changed(glib::clone!(
+        #[strong]
+        refresh,
+        move |entry| refresh(entry)
+    ));
+    entry.connect_notify_local(Some("cursor-position"), move |entry, _| refresh(entry));
+
+    let key = gtk::EventControllerKey::new();
+    key.set_propagation_phase(gtk::PropagationPhase::Capture);
+    key.connect_key_pressed(glib::clone!(
+        #[weak]
+        entry,
+        #[weak]
+        popover,
+        #[weak]
+        list_box,
+        #[upgrade_or]
+        glib::Propagation::Proceed,
+        move |_, key, _, _| {
+            use gtk::gdk::Key;
+
+            if !popover.is_visible() {
+                return glib::Propagation::Proceed;
+            }
+
+            let selected = list_box.selected_row().map_or(0, |x| x.index());
+
+            match key {
+                Key::Down | Key::Up => {
+                    let index = if key == Key::Down {
+                        selected + 1
+                    } else {
+                        selected - 1
+                    };
+
+                    if let Some(row) = list_box.row_at_index(index) {
+                        list_box.select_row(Some(&row));
+                    }
+                }
+                Key::Tab | Key::Return | Key::KP_Enter => {
+                    if let Some(row) = list_box.selected_row() {
+                        complete(&entry, &popover, &row, !triggers.is_empty());
+                    }
+                }
+                Key::Escape => popover.popdown(),
+                _ => return glib::Propagation::Proceed,
+            }
+
+            glib::Propagation::Stop
+        }
+    ));
+    entry.add_controller(key);
+
+    let focus = gtk::EventControllerFocus::new();
+    focus.connect_enter(glib::clone!(
+        #[strong]
+        cache,
+        move |_| {
+            cache.replace(None);
+        }
+    ));
+    focus.connect_leave(move |_| {
+        cache.replace(None);
+    });
+    entry.add_controller(focus);
+
+    entry.connect_destroy(move |_| popover.unparent());
+}
+
+fn complete(entry: &gtk::Entry, popover: &gtk::Popover, row: &gtk::ListBoxRow, separate: bool) {
+    let Some(label) = row.child().and_downcast::<gtk::Label>() else {
+        return;
+    };
+
+    let (text, position) = crate::tasks::completion::complete(
+        &entry.text(),
+        entry.position() as usize,
+        &label.text(),
+        separate,
+    );
+
+    popover.popdown();
+    entry.set_text(&text);
+    entry.set_position(position as i32);
+}
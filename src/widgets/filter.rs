@@ -1,4 +1,5 @@
 use adw::prelude::*;
+use gtk::glib;
 use relm4::ComponentController as _;
 
 #[repr(u32)]
@@ -7,6 +8,8 @@ enum Column {
     Raw = 1,
     Progress = 2,
     Tooltip = 3,
+    Color = 4,
+    Bullet = 5,
 }
 
 impl From<Column> for u32 {
@@ -23,25 +26,48 @@ impl From<Column> for i32 {
 
 #[derive(Debug)]
 pub enum MsgInput {
+    /// Applies the change picked in the context menu.
+    Change(crate::tasks::rename::Change),
+    Drop(usize, gtk::TreePath, bool),
+    /// Opens the context menu of the row at the given position.
+    Menu(gtk::TreePath, f64, f64),
     SelectionChange,
-    UpdateFilters(Vec<(String, (u32, u32))>),
+    UpdateFilters(Vec<Filter>),
+    /// Describes the selected project above its tasks.
+    UpdateHeader(Option<(String, crate::tasks::project::Metadata)>),
     UpdateTasks(Vec<crate::tasks::Task>),
 }
 
+/// A tree entry: its name, done and total tasks, and the project metadata.
+pub type Filter = (String, (u32, u32), Option<crate::tasks::project::Metadata>);
+
 #[derive(Debug)]
 pub enum MsgOutput {
+    Attach(Box<crate::tasks::Task>, Vec<std::path::PathBuf>),
+    Bulk(Vec<usize>, crate::tasks::bulk::Action),
     Complete(Box<crate::tasks::Task>),
+    Delete(Vec<crate::tasks::Task>),
     Edit(Box<crate::tasks::Task>),
     Filters(Vec<String>),
+    /// Asks to rename, move or merge a filter.
+    Rename(crate::tasks::rename::Change, String),
+    Save(Box<crate::tasks::Task>),
+    /// A task was dropped on a filter, replacing its tags of this kind when
+    /// the last field is set.
+    Tag(usize, String, bool),
+    Timer(Box<crate::tasks::Task>),
 }
 
 pub struct Model {
     filters: std::collections::BTreeMap<gtk::TreePath, String>,
+    /// Context menu of the tree and the filter it was opened on.
+    menu: gtk::Popover,
+    menu_filter: Option<String>,
     tasks: relm4::Controller<super::tasks::Model>,
 }
 
 impl Model {
-    fn update_filters(&mut self, widgets: &ModelWidgets, filters: Vec<(String, (u32, u32))>) {
+    fn update_filters(&mut self, widgets: &ModelWidgets, filters: Vec<Filter>) {
         let selection = widgets.tree_view.selection();
         let (paths, _) = selection.selected_rows();
 
@@ -64,10 +90,10 @@ impl Model {
         &mut self,
         widgets: &ModelWidgets,
         root: &mut std::collections::HashMap<String, gtk::TreeIter>,
-        filter: (String, (u32, u32)),
+        filter: Filter,
     ) {
         let separator = '\\';
-        let (filter, (done, total)) = filter;
+        let (filter, (done, total), metadata) = filter;
         let progress = (done as f32 / total as f32) * 100.;
         let f = filter.clone();
 
@@ -77,7 +103,7 @@ impl Model {
         let parent = levels.join(&separator.to_string());
 
         if !parent.is_empty() && root.get(&parent).is_none() {
-            self.append(widgets, root, (parent.clone(), (0, 0)));
+            self.append(widgets, root, (parent.clone(), (0, 0), None));
         }
 
         let row = widgets.store.append(root.get(&parent));
@@ -92,10 +118,35 @@ impl Model {
             .store
             .set_value(&row, Column::Progress.into(), &progress.to_value());
 
-        let tooltip = format!("{done}/{total}");
+        let mut tooltip = format!("{done}/{total}");
+        let mut color = None;
+
+        if let Some(metadata) = metadata {
+            tooltip.push_str(&format!("\n{}", metadata.summary()));
+
+            if !metadata.description.is_empty() {
+                tooltip.push_str(&format!(
+                    "\n{}",
+                    crate::tasks::markup::escape(&metadata.description)
+                ));
+            }
+
+            color = metadata
+                .color
+                .filter(|x| gtk::gdk::RGBA::parse(x.as_str()).is_ok());
+        }
+
         widgets
             .store
             .set_value(&row, Column::Tooltip.into(), &tooltip.to_value());
+        widgets
+            .store
+            .set_value(&row, Column::Color.into(), &color.to_value());
+        widgets.store.set_value(
+            &row,
+            Column::Bullet.into(),
+            &if color.is_some() { "●" } else { "" }.to_value(),
+        );
 
         root.insert(filter.clone(), row);
 
@@ -103,10 +154,130 @@ impl Model {
         self.filters.insert(path, filter);
     }
 
+    fn update_header(
+        &self,
+        widgets: &ModelWidgets,
+        header: Option<(String, crate::tasks::project::Metadata)>,
+    ) {
+        let Some((name, metadata)) = header else {
+            widgets.header.set_visible(false);
+            return;
+        };
+
+        let name = crate::tasks::markup::escape(&name);
+        let color = metadata
+            .color
+            .as_ref()
+            .filter(|x| gtk::gdk::RGBA::parse(x.as_str()).is_ok());
+        let title = match color {
+            Some(color) => format!(
+                "<span foreground=\"{}\">●</span> <b>{name}</b>",
+                crate::tasks::markup::escape(color)
+            ),
+            None => format!("<b>{name}</b>"),
+        };
+
+        widgets.header_title.set_markup(&title);
+        widgets.header_summary.set_text(&metadata.summary());
+        widgets.header_description.set_text(&metadata.description);
+        widgets
+            .header_description
+            .set_visible(!metadata.description.is_empty());
+        widgets.header.set_visible(true);
+    }
+
     fn update_tasks(&self, tasks: Vec<crate::tasks::Task>) {
         self.tasks.emit(super::tasks::MsgInput::Update(tasks));
     }
 
+    /// Accepts tasks dropped on a filter row, with shift to replace the tags.
+    fn add_drop_target(tree_view: &gtk::TreeView, sender: relm4::ComponentSender<Self>) {
+        let target = super::task::task_drop_target(glib::clone!(
+            #[weak]
+            tree_view,
+            #[upgrade_or]
+            false,
+            move |id, x, y, modifiers| {
+                tree_view.set_drag_dest_row(None, gtk::TreeViewDropPosition::IntoOrBefore);
+
+                let Some((Some(path), _)) = tree_view.dest_row_at_pos(x as i32, y as i32) else {
+                    return false;
+                };
+                let replace = modifiers.contains(gtk::gdk::ModifierType::SHIFT_MASK);
+                sender.input(MsgInput::Drop(id, path, replace));
+
+                true
+            }
+        ));
+
+        target.connect_motion(glib::clone!(
+            #[weak]
+            tree_view,
+            #[upgrade_or]
+            gtk::gdk::DragAction::empty(),
+            move |_, x, y| {
+                let path = tree_view
+                    .dest_row_at_pos(x as i32, y as i32)
+                    .and_then(|(path, _)| path);
+
+                tree_view.set_drag_dest_row(path.as_ref(), gtk::TreeViewDropPosition::IntoOrBefore);
+
+                if path.is_some() {
+                    gtk::gdk::DragAction::COPY
+                } else {
+                    gtk::gdk::DragAction::empty()
+                }
+            }
+        ));
+
+        target.connect_leave(glib::clone!(
+            #[weak]
+            tree_view,
+            move |_| tree_view.set_drag_dest_row(None, gtk::TreeViewDropPosition::IntoOrBefore)
+        ));
+
+        tree_view.add_controller(target);
+    }
+
+    /// Opens the context menu on right click.
+    fn add_menu(&self, tree_view: &gtk::TreeView, sender: relm4::ComponentSender<Self>) {
+        use crate::tasks::rename::Change;
+
+        let list = gtk::Box::new(gtk::Orientation::Vertical, 0);
+
+        for (label, change) in [
+            ("Rename…", Change::Rename),
+            ("Move…", Change::Move),
+            ("Merge into…", Change::Merge),
+        ] {
+            let button = gtk::Button::with_label(label);
+            button.add_css_class("flat");
+            button.connect_clicked(glib::clone!(
+                #[strong]
+                sender,
+                move |_| sender.input(MsgInput::Change(change))
+            ));
+            list.append(&button);
+        }
+
+        self.menu.set_child(Some(&list));
+        self.menu.set_has_arrow(false);
+        self.menu.set_parent(tree_view);
+
+        let gesture = gtk::GestureClick::new();
+        gesture.set_button(gtk::gdk::BUTTON_SECONDARY);
+        gesture.connect_pressed(glib::clone!(
+            #[weak]
+            tree_view,
+            move |_, _, x, y| {
+                if let Some((Some(path), _, _, _)) = tree_view.path_at_pos(x as i32, y as i32) {
+                    sender.input(MsgInput::Menu(path, x, y));
+                }
+            }
+        ));
+        tree_view.add_controller(gesture);
+    }
+
     fn select_range(treeview: &gtk::TreeView, path: &gtk::TreePath) {
         let model = treeview.model().unwrap();
 
@@ -143,6 +314,11 @@ impl relm4::Component for Model {
             .forward(sender.output_sender(), |output| match output {
                 super::task::MsgOutput::Complete(task) => MsgOutput::Complete(task),
                 super::task::MsgOutput::Edit(task) => MsgOutput::Edit(task),
+                super::task::MsgOutput::Attach(task, files) => MsgOutput::Attach(task, files),
+                super::task::MsgOutput::Save(task) => MsgOutput::Save(task),
+                super::task::MsgOutput::Bulk(ids, action) => MsgOutput::Bulk(ids, action),
+                super::task::MsgOutput::Delete(tasks) => MsgOutput::Delete(tasks),
+                super::task::MsgOutput::Timer(task) => MsgOutput::Timer(task),
             });
 
         let columns = vec![
@@ -150,24 +326,38 @@ impl relm4::Component for Model {
             gtk::glib::types::Type::STRING,
             gtk::glib::types::Type::U32,
             gtk::glib::types::Type::STRING,
+            gtk::glib::types::Type::STRING,
+            gtk::glib::types::Type::STRING,
         ];
 
         let model = Self {
             tasks,
             filters: std::collections::BTreeMap::new(),
+            menu: gtk::Popover::new(),
+            menu_filter: None,
         };
 
         let widgets = view_output!();
 
         let selection = widgets.tree_view.selection();
         selection.set_mode(gtk::SelectionMode::Multiple);
-        selection.connect_changed(move |_| {
-            sender.input(MsgInput::SelectionChange);
-        });
+        selection.connect_changed(glib::clone!(
+            #[strong]
+            sender,
+            move |_| sender.input(MsgInput::SelectionChange)
+        ));
+
+        model.add_menu(&widgets.tree_view, sender.clone());
+        Self::add_drop_target(&widgets.tree_view, sender);
 
         let column = gtk::TreeViewColumn::new();
         widgets.tree_view.append_column(&column);
 
+        let bullet = gtk::CellRendererText::new();
+        gtk::prelude::CellLayoutExt::pack_start(&column, &bullet, false);
+        column.add_attribute(&bullet, "text", Column::Bullet.into());
+        column.add_attribute(&bullet, "foreground", Column::Color.into());
+
         let cell = gtk::CellRendererProgress::new();
         cell.set_text_xalign(0.);
         gtk::prelude::CellLayoutExt::pack_start(&column, &cell, true);
@@ -189,6 +379,28 @@ impl relm4::Component for Model {
         use MsgInput::*;
 
         match msg {
+            Change(change) => {
+                self.menu.popdown();
+
+                if let Some(filter) = self.menu_filter.take() {
+                    sender.output(MsgOutput::Rename(change, filter)).ok();
+                }
+            }
+            Drop(id, path, replace) => {
+                if let Some(filter) = self.filters.get(&path) {
+                    sender
+                        .output(MsgOutput::Tag(id, filter.clone(), replace))
+                        .ok();
+                }
+            }
+            Menu(path, x, y) => {
+                if let Some(filter) = self.filters.get(&path) {
+                    self.menu_filter = Some(filter.clone());
+                    self.menu
+                        .set_pointing_to(Some(&gtk::gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+                    self.menu.popup();
+                }
+            }
             SelectionChange => {
                 let mut filters = Vec::new();
 
@@ -204,6 +416,7 @@ impl relm4::Component for Model {
                 sender.output(MsgOutput::Filters(filters)).ok();
             }
             UpdateFilters(filters) => self.update_filters(widgets, filters),
+            UpdateHeader(header) => self.update_header(widgets, header),
             UpdateTasks(tasks) => self.update_tasks(tasks),
         }
     }
@@ -230,8 +443,34 @@ impl relm4::Component for Model {
                 },
             },
             #[wrap(Some)]
-            set_end_child = &gtk::ScrolledWindow {
-                set_child: Some(model.tasks.widget()),
+            set_end_child = &gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                #[name = "header"]
+                gtk::Box {
+                    add_css_class: "project-header",
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_visible: false,
+
+                    #[name = "header_title"]
+                    gtk::Label {
+                        set_xalign: 0.,
+                    },
+                    #[name = "header_summary"]
+                    gtk::Label {
+                        add_css_class: "summary",
+                        set_xalign: 0.,
+                    },
+                    #[name = "header_description"]
+                    gtk::Label {
+                        set_wrap: true,
+                        set_xalign: 0.,
+                    },
+                },
+                gtk::ScrolledWindow {
+                    set_vexpand: true,
+                    set_child: Some(model.tasks.widget()),
+                },
             },
         }
     }
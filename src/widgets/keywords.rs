@@ -162,6 +162,14 @@ impl relm4::Component for Model {
             ))
         ));
 
+        cell.connect_editing_started(|_, editable, _| {
+            if let Some(entry) = editable.downcast_ref::<gtk::Entry>() {
+                crate::widgets::completion::attach(entry, "", || {
+                    crate::tasks::completion::keys(&crate::application::tasks())
+                });
+            }
+        });
+
         gtk::prelude::CellLayoutExt::pack_start(&column, &cell, true);
         column.add_attribute(&cell, "text", Column::Name.into());
 
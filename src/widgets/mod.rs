@@ -1,7 +1,11 @@
+pub mod burndown;
 pub mod calendar;
+pub mod chart;
 pub mod circle;
+pub mod completion;
 pub mod filter;
 pub mod keywords;
+pub mod note;
 pub mod priority;
 pub mod recurrence;
 pub mod tags;
@@ -0,0 +1,376 @@
+use adw::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Action {
+    Heading(usize),
+    Bullet,
+    Numbered,
+    Task,
+    Quote,
+    Bold,
+    Italic,
+    Strikethrough,
+    Code,
+    CodeBlock,
+    Link,
+    Table,
+}
+
+#[derive(Debug)]
+pub enum MsgInput {
+    Apply(Action),
+    Changed,
+    Set(String),
+}
+
+#[derive(Debug)]
+pub enum MsgOutput {
+    Updated(String),
+}
+
+pub struct Model;
+
+impl Model {
+    fn apply(&self, widgets: &ModelWidgets, action: Action) {
+        use Action::*;
+
+        let buffer = &widgets.buffer;
+        buffer.begin_user_action();
+
+        match action {
+            Heading(level) => Self::prefix(buffer, &format!("{} ", "#".repeat(level))),
+            Bullet => Self::prefix(buffer, "- "),
+            Numbered => Self::prefix(buffer, "1. "),
+            Task => Self::prefix(buffer, "- [ ] "),
+            Quote => Self::prefix(buffer, "> "),
+            Bold => Self::wrap(buffer, "**", "**"),
+            Italic => Self::wrap(buffer, "_", "_"),
+            Strikethrough => Self::wrap(buffer, "~~", "~~"),
+            Code => Self::wrap(buffer, "`", "`"),
+            CodeBlock => Self::wrap(buffer, "\n```\n", "\n```\n"),
+            Link => Self::wrap(buffer, "[", "](https://)"),
+            Table => Self::wrap(
+                buffer,
+                "\n| Column | Column |\n| ------ | ------ |\n| ",
+                " |        |\n",
+            ),
+        }
+
+        buffer.end_user_action();
+        widgets.text_view.grab_focus();
+    }
+
+    /// Inserts `prefix` at the start of every selected line.
+    fn prefix(buffer: &gtk::TextBuffer, prefix: &str) {
+        let (start, end) = buffer.selection_bounds().unwrap_or_else(|| {
+            let cursor = buffer.iter_at_mark(&buffer.get_insert());
+            (cursor, cursor)
+        });
+
+        for line in start.line()..=end.line() {
+            if let Some(mut iter) = buffer.iter_at_line(line) {
+                buffer.insert(&mut iter, prefix);
+            }
+        }
+    }
+
+    /// Surrounds the selection, or puts the cursor between `before` and
+    /// `after` when nothing is selected.
+    fn wrap(buffer: &gtk::TextBuffer, before: &str, after: &str) {
+        if let Some((mut start, mut end)) = buffer.selection_bounds() {
+            let text = buffer.text(&start, &end, false);
+
+            buffer.delete(&mut start, &mut end);
+            buffer.insert(&mut start, &format!("{before}{text}{after}"));
+        } else {
+            let mut iter = buffer.iter_at_mark(&buffer.get_insert());
+
+            buffer.insert(&mut iter, &format!("{before}{after}"));
+            iter.backward_chars(after.chars().count() as i32);
+            buffer.place_cursor(&iter);
+        }
+    }
+
+    fn changed(&self, widgets: &ModelWidgets, sender: &relm4::ComponentSender<Self>) {
+        let buffer = &widgets.buffer;
+        let text = buffer
+            .text(&buffer.start_iter(), &buffer.end_iter(), true)
+            .to_string();
+
+        buffer.remove_all_tags(&buffer.start_iter(), &buffer.end_iter());
+
+        for (tag, start, end) in highlights(&text) {
+            buffer.apply_tag_by_name(
+                tag,
+                &buffer.iter_at_offset(start as i32),
+                &buffer.iter_at_offset(end as i32),
+            );
+        }
+
+        widgets
+            .preview
+            .set_markup(&crate::tasks::markup::markdown(&text));
+
+        sender.output(MsgOutput::Updated(text)).ok();
+    }
+
+    fn create_tags(buffer: &gtk::TextBuffer) {
+        let table = buffer.tag_table();
+
+        let tags = [
+            gtk::TextTag::builder()
+                .name("heading")
+                .weight(700)
+                .scale(1.2)
+                .build(),
+            gtk::TextTag::builder()
+                .name("emphasis")
+                .style(gtk::pango::Style::Italic)
+                .build(),
+            gtk::TextTag::builder().name("strong").weight(700).build(),
+            gtk::TextTag::builder()
+                .name("strikethrough")
+                .strikethrough(true)
+                .build(),
+            gtk::TextTag::builder()
+                .name("code")
+                .family("monospace")
+                .foreground("#C061CB")
+                .build(),
+            gtk::TextTag::builder()
+                .name("link")
+                .foreground("#3584E4")
+                .underline(gtk::pango::Underline::Single)
+                .build(),
+            gtk::TextTag::builder()
+                .name("quote")
+                .foreground("gray")
+                .style(gtk::pango::Style::Italic)
+                .build(),
+            gtk::TextTag::builder()
+                .name("marker")
+                .foreground("#3584E4")
+                .weight(700)
+                .build(),
+        ];
+
+        for tag in tags {
+            table.add(&tag);
+        }
+    }
+}
+
+/// Text tags to apply on the editor, as `(tag, start, end)` character offsets.
+fn highlights(text: &str) -> Vec<(&'static str, usize, usize)> {
+    use pulldown_cmark::{Event, Tag};
+
+    // Byte offset to char offset, the text buffer counts in chars
+    let mut offsets = vec![0; text.len() + 1];
+    for (n, (byte, c)) in text.char_indices().enumerate() {
+        offsets[byte..byte + c.len_utf8()].fill(n);
+    }
+    offsets[text.len()] = text.chars().count();
+
+    let parser =
+        pulldown_cmark::Parser::new_ext(text, crate::tasks::markup::options()).into_offset_iter();
+
+    parser
+        .filter_map(|(event, range)| {
+            let tag = match event {
+                Event::Start(Tag::Heading { .. }) => "heading",
+                Event::Start(Tag::Emphasis) => "emphasis",
+                Event::Start(Tag::Strong) => "strong",
+                Event::Start(Tag::Strikethrough) => "strikethrough",
+                Event::Start(Tag::CodeBlock(_)) | Event::Code(_) => "code",
+                Event::Start(Tag::Link { .. }) => "link",
+                Event::Start(Tag::BlockQuote(_)) => "quote",
+                Event::TaskListMarker(_) => "marker",
+                _ => return None,
+            };
+
+            Some((tag, offsets[range.start], offsets[range.end]))
+        })
+        .collect()
+}
+
+#[relm4::component(pub)]
+impl relm4::Component for Model {
+    type CommandOutput = ();
+    type Init = ();
+    type Input = MsgInput;
+    type Output = MsgOutput;
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        let model = Self;
+
+        let widgets = view_output!();
+
+        Self::create_tags(&widgets.buffer);
+
+        widgets
+            .preview_button
+            .bind_property("active", &widgets.preview_window, "visible")
+            .sync_create()
+            .build();
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        use MsgInput::*;
+
+        match msg {
+            Apply(action) => self.apply(widgets, action),
+            Changed => self.changed(widgets, &sender),
+            Set(text) => widgets.buffer.set_text(&text),
+        }
+    }
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+
+            gtk::Box {
+                add_css_class: "toolbar",
+                set_orientation: gtk::Orientation::Horizontal,
+
+                gtk::Button {
+                    set_label: "H1",
+                    set_tooltip_text: Some("Heading"),
+                    connect_clicked => MsgInput::Apply(Action::Heading(1)),
+                },
+                gtk::Button {
+                    set_label: "H2",
+                    set_tooltip_text: Some("Sub-heading"),
+                    connect_clicked => MsgInput::Apply(Action::Heading(2)),
+                },
+                gtk::Button {
+                    set_icon_name: "format-text-bold",
+                    set_tooltip_text: Some("Bold"),
+                    connect_clicked => MsgInput::Apply(Action::Bold),
+                },
+                gtk::Button {
+                    set_icon_name: "format-text-italic",
+                    set_tooltip_text: Some("Italic"),
+                    connect_clicked => MsgInput::Apply(Action::Italic),
+                },
+                gtk::Button {
+                    set_icon_name: "format-text-strikethrough",
+                    set_tooltip_text: Some("Strikethrough"),
+                    connect_clicked => MsgInput::Apply(Action::Strikethrough),
+                },
+                gtk::Button {
+                    set_icon_name: "view-list-bullet",
+                    set_tooltip_text: Some("List"),
+                    connect_clicked => MsgInput::Apply(Action::Bullet),
+                },
+                gtk::Button {
+                    set_icon_name: "view-list-ordered",
+                    set_tooltip_text: Some("Numbered list"),
+                    connect_clicked => MsgInput::Apply(Action::Numbered),
+                },
+                gtk::Button {
+                    set_icon_name: "checkbox-checked-symbolic",
+                    set_tooltip_text: Some("Task list"),
+                    connect_clicked => MsgInput::Apply(Action::Task),
+                },
+                gtk::Button {
+                    set_icon_name: "format-indent-more",
+                    set_tooltip_text: Some("Quote"),
+                    connect_clicked => MsgInput::Apply(Action::Quote),
+                },
+                gtk::Button {
+                    set_icon_name: "insert-link",
+                    set_tooltip_text: Some("Link"),
+                    connect_clicked => MsgInput::Apply(Action::Link),
+                },
+                gtk::Button {
+                    set_label: "</>",
+                    set_tooltip_text: Some("Inline code"),
+                    connect_clicked => MsgInput::Apply(Action::Code),
+                },
+                gtk::Button {
+                    set_label: "{ }",
+                    set_tooltip_text: Some("Code block"),
+                    connect_clicked => MsgInput::Apply(Action::CodeBlock),
+                },
+                gtk::Button {
+                    set_icon_name: "x-office-spreadsheet",
+                    set_tooltip_text: Some("Table"),
+                    connect_clicked => MsgInput::Apply(Action::Table),
+                },
+                #[name = "preview_button"]
+                gtk::ToggleButton {
+                    set_active: true,
+                    set_halign: gtk::Align::End,
+                    set_hexpand: true,
+                    set_icon_name: "view-reveal-symbolic",
+                    set_tooltip_text: Some("Preview"),
+                },
+            },
+            gtk::Paned {
+                set_orientation: gtk::Orientation::Vertical,
+                set_vexpand: true,
+                set_wide_handle: true,
+
+                #[wrap(Some)]
+                set_start_child = &gtk::ScrolledWindow {
+                    set_size_request: (-1, 100),
+
+                    #[name = "text_view"]
+                    gtk::TextView {
+                        add_css_class: "note",
+                        set_hexpand: true,
+                        set_monospace: true,
+                        set_wrap_mode: gtk::WrapMode::WordChar,
+                        #[wrap(Some)]
+                        #[name = "buffer"]
+                        set_buffer = &gtk::TextBuffer {
+                            connect_changed => MsgInput::Changed,
+                        },
+                    },
+                },
+                #[wrap(Some)]
+                #[name = "preview_window"]
+                set_end_child = &gtk::ScrolledWindow {
+                    set_size_request: (-1, 100),
+
+                    #[name = "preview"]
+                    gtk::Label {
+                        add_css_class: "preview",
+                        set_selectable: true,
+                        set_wrap: true,
+                        set_xalign: 0.,
+                        set_yalign: 0.,
+                    },
+                },
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn highlights() {
+        assert_eq!(
+            super::highlights("# Été\n\n*a* `b` [c](d)"),
+            vec![
+                ("heading", 0, 6),
+                ("emphasis", 7, 10),
+                ("code", 11, 14),
+                ("link", 15, 21),
+            ]
+        );
+    }
+}
@@ -10,42 +10,229 @@ pub enum Type {
 
 #[derive(Debug)]
 pub enum MsgInput {
+    /// Asks to rename, move or merge a tag, with a confirmation.
+    Ask(crate::tasks::rename::Change, String),
+    Attach(Box<crate::tasks::Task>, Vec<std::path::PathBuf>),
+    Bulk(Vec<usize>, crate::tasks::bulk::Action),
+    Color(String),
     Complete(Box<crate::tasks::Task>),
+    Deadline(String),
+    Delete(Vec<crate::tasks::Task>),
+    Description(String),
     Edit(Box<crate::tasks::Task>),
+    Save(Box<crate::tasks::Task>),
+    SaveDetails,
+    Status(u32),
+    Tag(usize, String, bool),
+    Timer(Box<crate::tasks::Task>),
     UpdateFilters(Vec<String>),
     Update,
 }
 
 #[derive(Debug)]
 pub enum MsgOutput {
+    Attach(Box<crate::tasks::Task>, Vec<std::path::PathBuf>),
+    Bulk(Vec<usize>, crate::tasks::bulk::Action),
     Complete(Box<crate::tasks::Task>),
+    Delete(Vec<crate::tasks::Task>),
     Edit(Box<crate::tasks::Task>),
+    /// Renames the tag with the given prefix, from and to names.
+    RenameTag(char, String, String),
+    Save(Box<crate::tasks::Task>),
+    Timer(Box<crate::tasks::Task>),
 }
 
 pub struct Model {
     tag: Type,
     filter: relm4::Controller<super::filter::Model>,
+    filters: Vec<String>,
+    /// Tasks using the selected tag or its sub-tags.
+    count: usize,
+    projects: crate::tasks::project::Projects,
+    /// Metadata of the selected project, as edited.
+    details: crate::tasks::project::Metadata,
+    deadline: String,
+    /// Why the details couldn't be saved.
+    details_error: String,
+    stats: crate::tasks::stats::Stats,
+    burndown: relm4::Controller<super::burndown::Model>,
+    selection_changed: bool,
 }
 
 impl Model {
-    fn update_tags(&self) {
+    fn prefix(&self) -> char {
+        match self.tag {
+            Type::Projects => '+',
+            Type::Contexts => '@',
+            Type::Hashtags => '#',
+        }
+    }
+
+    fn names(tag: Type) -> Vec<String> {
         let list = crate::application::tasks();
+
+        match tag {
+            Type::Projects => list.projects(),
+            Type::Contexts => list.contexts(),
+            Type::Hashtags => list.hashtags(),
+        }
+    }
+
+    fn selection_label(&self) -> String {
+        match self.filters.as_slice() {
+            [name] => format!("{}{name} · {} tasks", self.prefix(), self.count),
+            _ => String::new(),
+        }
+    }
+
+    /// Asks for the new name of `from`, with the number of tasks changed.
+    fn ask(
+        &self,
+        sender: &relm4::ComponentSender<Self>,
+        change: crate::tasks::rename::Change,
+        from: String,
+    ) {
+        use gtk::glib;
+
+        let prefix = self.prefix();
+        let count =
+            crate::tasks::rename::tagged(&crate::application::tasks().tasks, prefix, &from).len();
+
+        let dialog = adw::AlertDialog::new(
+            Some(&format!("{} {prefix}{from}?", change.label())),
+            Some(&format!(
+                "{count} tasks using {prefix}{from} or its sub-tags will be changed."
+            )),
+        );
+        dialog.add_responses(&[("cancel", "Cancel"), ("apply", change.label())]);
+        dialog.set_response_appearance("apply", adw::ResponseAppearance::Suggested);
+        dialog.set_response_enabled("apply", change.target(prefix, &from, "").is_some());
+        dialog.set_default_response(Some("apply"));
+        dialog.set_close_response("cancel");
+
+        let entry = gtk::Entry::new();
+        entry.set_placeholder_text(Some(change.placeholder()));
+        entry.set_activates_default(true);
+
+        if change != crate::tasks::rename::Change::Rename {
+            let tag = self.tag;
+            crate::widgets::completion::attach(&entry, "", move || Self::names(tag));
+        }
+
+        entry.connect_changed(glib::clone!(
+            #[weak]
+            dialog,
+            #[strong]
+            from,
+            move |entry| {
+                let valid = change.target(prefix, &from, &entry.text()).is_some();
+                dialog.set_response_enabled("apply", valid);
+            }
+        ));
+        dialog.set_extra_child(Some(&entry));
+
+        let sender = sender.clone();
+
+        dialog.connect_response(
+            None,
+            glib::clone!(
+                #[weak]
+                entry,
+                move |_, response| {
+                    if response != "apply" {
+                        return;
+                    }
+
+                    if let Some(to) = change.target(prefix, &from, &entry.text()) {
+                        sender
+                            .output(MsgOutput::RenameTag(prefix, from.clone(), to))
+                            .ok();
+                    }
+                }
+            ),
+        );
+
+        dialog.present(Some(self.filter.widget()));
+    }
+
+    fn update_tags(&mut self) {
+        use crate::tasks::project::Status;
+
+        let list = crate::application::tasks();
+        let preferences = crate::application::preferences();
         let tags = match self.tag {
             Type::Projects => list.projects(),
             Type::Contexts => list.contexts(),
             Type::Hashtags => list.hashtags(),
         };
 
+        if matches!(self.tag, Type::Projects) {
+            self.projects = crate::tasks::project::load();
+        }
+
         let tags = tags
             .iter()
-            .map(|x| (x.clone(), self.progress(&list, x)))
-            .filter(|&(_, (done, total))| done != total)
+            .map(|x| {
+                let metadata = self.projects.get(x).cloned();
+                (x.clone(), self.progress(&list, x), metadata)
+            })
+            .filter(|(_, (done, total), metadata)| {
+                let status = metadata.as_ref().map(|x| x.status).unwrap_or_default();
+
+                preferences.completed || (done != total && status != Status::Done)
+            })
             .collect();
 
         self.filter
             .emit(crate::widgets::filter::MsgInput::UpdateFilters(tags));
     }
 
+    fn save_details(&mut self) {
+        let [name] = self.filters.as_slice() else {
+            return;
+        };
+
+        let deadline = self.deadline.trim();
+        self.details.deadline = if deadline.is_empty() {
+            None
+        } else {
+            match chrono::NaiveDate::parse_from_str(deadline, "%Y-%m-%d") {
+                Ok(deadline) => Some(deadline),
+                Err(err) => {
+                    self.details_error = format!("Invalid deadline {deadline:?}: {err}");
+                    return;
+                }
+            }
+        };
+
+        if let Some(color) = &self.details.color
+            && gtk::gdk::RGBA::parse(color.as_str()).is_err()
+        {
+            self.details_error = format!("Invalid color {color:?}");
+            return;
+        }
+
+        self.details_error.clear();
+
+        if self.details.is_empty() {
+            self.projects.remove(name);
+        } else {
+            self.projects.insert(name.clone(), self.details.clone());
+        }
+
+        match crate::tasks::project::save(&self.projects) {
+            Ok(_) => log::info!("Project {name} updated"),
+            Err(err) => {
+                log::error!("{err}");
+                self.details_error = err;
+            }
+        }
+
+        let filters = self.filters.clone();
+        self.update_tags();
+        self.update_tasks(&filters);
+    }
+
     fn progress(&self, list: &crate::tasks::List, current: &str) -> (u32, u32) {
         list.tasks
             .iter()
@@ -67,29 +254,82 @@ impl Model {
             })
     }
 
-    fn update_tasks(&self, filters: &[String]) {
+    fn update_tasks(&mut self, filters: &[String]) {
+        self.filters = filters.to_vec();
+
+        if let [name] = filters {
+            let list = crate::application::tasks();
+            let tasks = crate::tasks::rename::tagged(&list.tasks, self.prefix(), name)
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>();
+
+            self.count = tasks.len();
+            self.stats = crate::tasks::stats::Stats::new(&tasks, crate::date::today());
+            self.burndown.emit(super::burndown::MsgInput::Update(
+                self.stats.burndown.clone(),
+            ));
+            self.selection_changed = true;
+        }
+
+        let mut header = None;
+
+        if let (Type::Projects, [name]) = (self.tag, filters) {
+            self.details_error.clear();
+            self.details = self.projects.get(name).cloned().unwrap_or_default();
+            self.deadline = self
+                .details
+                .deadline
+                .map(|x| x.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            self.selection_changed = true;
+
+            if !self.details.is_empty() {
+                header = Some((name.clone(), self.details.clone()));
+            }
+        }
+
+        self.filter
+            .emit(crate::widgets::filter::MsgInput::UpdateHeader(header));
+
+        self.filter
+            .emit(crate::widgets::filter::MsgInput::UpdateTasks(self.tasks()));
+    }
+
+    /// Tasks of the selected projects, contexts or hashtags.
+    pub fn tasks(&self) -> Vec<crate::tasks::Task> {
         let today = crate::date::today();
         let preferences = crate::application::preferences();
         let list = crate::application::tasks();
 
-        let tasks = list
-            .tasks
+        list.tasks
             .iter()
             .filter(|x| {
                 let tags = self.tags(x);
 
                 (preferences.done || !x.finished)
                     && !tags.is_empty()
-                    && Self::has_filter(tags, filters)
+                    && Self::has_filter(tags, &self.filters)
                     && (preferences.defered
                         || x.threshold_date.is_none()
                         || x.threshold_date.unwrap() <= today)
             })
             .cloned()
-            .collect();
+            .collect()
+    }
 
-        self.filter
-            .emit(crate::widgets::filter::MsgInput::UpdateTasks(tasks));
+    pub fn title(&self) -> String {
+        let title = match self.tag {
+            Type::Projects => "Projects",
+            Type::Contexts => "Contexts",
+            Type::Hashtags => "Tags",
+        };
+
+        if self.filters.is_empty() {
+            title.to_string()
+        } else {
+            format!("{title} {}", self.filters.join(", "))
+        }
     }
 
     fn tags<'a>(&self, task: &'a crate::tasks::Task) -> &'a [String] {
@@ -134,26 +374,94 @@ impl relm4::SimpleComponent for Model {
                 .forward(sender.input_sender(), |output| match output {
                     super::filter::MsgOutput::Complete(task) => MsgInput::Complete(task),
                     super::filter::MsgOutput::Edit(task) => MsgInput::Edit(task),
+                    super::filter::MsgOutput::Attach(task, files) => MsgInput::Attach(task, files),
                     super::filter::MsgOutput::Filters(filters) => MsgInput::UpdateFilters(filters),
+                    super::filter::MsgOutput::Rename(change, name) => MsgInput::Ask(change, name),
+                    super::filter::MsgOutput::Save(task) => MsgInput::Save(task),
+                    super::filter::MsgOutput::Bulk(ids, action) => MsgInput::Bulk(ids, action),
+                    super::filter::MsgOutput::Delete(tasks) => MsgInput::Delete(tasks),
+                    super::filter::MsgOutput::Tag(id, tag, replace) => {
+                        MsgInput::Tag(id, tag, replace)
+                    }
+                    super::filter::MsgOutput::Timer(task) => MsgInput::Timer(task),
                 });
 
-        let model = Self { tag: init, filter };
+        let model = Self {
+            tag: init,
+            filter,
+            filters: Vec::new(),
+            count: 0,
+            projects: crate::tasks::project::Projects::new(),
+            details: crate::tasks::project::Metadata::default(),
+            deadline: String::new(),
+            details_error: String::new(),
+            stats: crate::tasks::stats::Stats::default(),
+            burndown: super::burndown::Model::builder().launch(()).detach(),
+            selection_changed: false,
+        };
 
         let widgets = view_output!();
 
+        model.filter.widget().set_vexpand(true);
+
         relm4::ComponentParts { model, widgets }
     }
 
     fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
         use MsgInput::*;
 
+        self.selection_changed = false;
+
         match msg {
+            Ask(change, name) => self.ask(&sender, change, name),
+            Attach(task, files) => {
+                sender.output(MsgOutput::Attach(task, files)).ok();
+            }
+            Bulk(ids, action) => {
+                sender.output(MsgOutput::Bulk(ids, action)).ok();
+            }
+            Color(color) => {
+                let color = color.trim();
+                self.details.color = (!color.is_empty()).then(|| color.to_string());
+            }
             Complete(task) => {
                 sender.output(MsgOutput::Complete(task)).ok();
             }
+            Deadline(deadline) => self.deadline = deadline,
+            Delete(tasks) => {
+                sender.output(MsgOutput::Delete(tasks)).ok();
+            }
+            Description(description) => self.details.description = description,
             Edit(task) => {
                 sender.output(MsgOutput::Edit(task)).ok();
             }
+            Save(task) => {
+                sender.output(MsgOutput::Save(task)).ok();
+            }
+            SaveDetails => self.save_details(),
+            Status(n) => {
+                self.details.status = crate::tasks::project::Status::ALL
+                    .get(n as usize)
+                    .copied()
+                    .unwrap_or_default();
+            }
+            Tag(id, tag, replace) => {
+                use crate::tasks::bulk::Action;
+
+                let action = match (self.tag, replace) {
+                    (Type::Projects, false) => Action::AddProject(tag),
+                    (Type::Projects, true) => Action::Move(tag),
+                    (Type::Contexts, false) => Action::AddContext(tag),
+                    (Type::Contexts, true) => Action::MoveContext(tag),
+                    (Type::Hashtags, false) => Action::AddHashtag(tag),
+                    (Type::Hashtags, true) => Action::MoveHashtag(tag),
+                };
+
+                sender.output(MsgOutput::Bulk(vec![id], action)).ok();
+            }
+            Timer(task) => {
+                sender.output(MsgOutput::Timer(task)).ok();
+            }
             Update => {
                 self.update_tags();
                 self.update_tasks(&[]);
@@ -164,7 +472,116 @@ impl relm4::SimpleComponent for Model {
 
     view! {
         gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+
             append: model.filter.widget(),
+
+            gtk::ActionBar {
+                #[watch]
+                set_revealed: model.filters.len() == 1,
+
+                pack_start = &gtk::Label {
+                    #[watch]
+                    set_text: &model.selection_label(),
+                },
+                pack_end = &gtk::MenuButton {
+                    set_label: "Statistics",
+                    #[wrap(Some)]
+                    set_popover = &gtk::Popover {
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 5,
+
+                            gtk::Label {
+                                #[track(model.selection_changed)]
+                                set_text: &model.stats.summary(),
+                            },
+                            gtk::Label {
+                                set_markup: "<span foreground=\"#ff6680\">open</span> and <span foreground=\"#4db366\">done</span> tasks by day",
+                            },
+                            append: model.burndown.widget(),
+                        },
+                    },
+                },
+                pack_end = &gtk::MenuButton {
+                    set_label: "Details",
+                    set_visible: matches!(model.tag, Type::Projects),
+                    #[wrap(Some)]
+                    set_popover = &gtk::Popover {
+                        gtk::Grid {
+                            set_column_spacing: 5,
+                            set_row_spacing: 5,
+
+                            attach[0, 0, 1, 1] = &gtk::Label {
+                                set_text: "Description",
+                                set_xalign: 0.,
+                            },
+                            attach[1, 0, 1, 1] = &gtk::Entry {
+                                #[track(model.selection_changed)]
+                                set_text: &model.details.description,
+                                connect_changed[sender] => move |entry| {
+                                    sender.input(MsgInput::Description(entry.text().to_string()));
+                                },
+                            },
+                            attach[0, 1, 1, 1] = &gtk::Label {
+                                set_text: "Deadline",
+                                set_xalign: 0.,
+                            },
+                            attach[1, 1, 1, 1] = &gtk::Entry {
+                                set_placeholder_text: Some("YYYY-MM-DD"),
+                                #[track(model.selection_changed)]
+                                set_text: &model.deadline,
+                                connect_changed[sender] => move |entry| {
+                                    sender.input(MsgInput::Deadline(entry.text().to_string()));
+                                },
+                            },
+                            attach[0, 2, 1, 1] = &gtk::Label {
+                                set_text: "Status",
+                                set_xalign: 0.,
+                            },
+                            attach[1, 2, 1, 1] = &gtk::DropDown {
+                                set_model: Some(&gtk::StringList::new(
+                                    &crate::tasks::project::Status::ALL.map(|x| x.as_str()),
+                                )),
+                                #[track(model.selection_changed)]
+                                set_selected: crate::tasks::project::Status::ALL
+                                    .iter()
+                                    .position(|x| *x == model.details.status)
+                                    .unwrap_or_default() as u32,
+                                connect_selected_notify[sender] => move |dropdown| {
+                                    sender.input(MsgInput::Status(dropdown.selected()));
+                                },
+                            },
+                            attach[0, 3, 1, 1] = &gtk::Label {
+                                set_text: "Color",
+                                set_xalign: 0.,
+                            },
+                            attach[1, 3, 1, 1] = &gtk::Entry {
+                                set_placeholder_text: Some("#3584e4"),
+                                #[track(model.selection_changed)]
+                                set_text: model.details.color.as_deref().unwrap_or_default(),
+                                connect_changed[sender] => move |entry| {
+                                    sender.input(MsgInput::Color(entry.text().to_string()));
+                                },
+                            },
+                            attach[0, 4, 2, 1] = &gtk::Label {
+                                add_css_class: "error",
+                                set_wrap: true,
+                                set_xalign: 0.,
+                                #[watch]
+                                set_text: &model.details_error,
+                                #[watch]
+                                set_visible: !model.details_error.is_empty(),
+                            },
+                            attach[1, 5, 1, 1] = &gtk::Button {
+                                add_css_class: "suggested-action",
+                                set_label: "Save",
+                                connect_clicked => MsgInput::SaveDetails,
+                            },
+                        },
+                    },
+                },
+            },
         }
     }
 }
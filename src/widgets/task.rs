@@ -1,17 +1,42 @@
 use adw::prelude::*;
+use gtk::glib;
 
 #[derive(Debug)]
 pub enum MsgInput {
+    Attach(Vec<std::path::PathBuf>),
+    Cancel,
     Click,
+    Defer(chrono::Duration),
+    Delete,
+    Edit,
+    Flag,
+    Plan,
+    Priority(todo_txt::Priority),
+    /// Only shows the task, for previews of tasks not in the list.
+    ReadOnly,
+    Rename(String),
+    Snippet(String),
+    Timer,
     Toggle,
 }
 #[derive(Debug)]
 pub enum MsgOutput {
+    Attach(Box<crate::tasks::Task>, Vec<std::path::PathBuf>),
+    Bulk(Vec<usize>, crate::tasks::bulk::Action),
     Complete(Box<crate::tasks::Task>),
+    Delete(Vec<crate::tasks::Task>),
     Edit(Box<crate::tasks::Task>),
+    Save(Box<crate::tasks::Task>),
+    /// Starts the pomodoro timer on the task.
+    Timer(Box<crate::tasks::Task>),
 }
 
 pub struct Model {
+    editing: bool,
+    /// Click, drag and drop controllers, disabled in read-only mode.
+    controllers: Vec<gtk::EventController>,
+    read_only: bool,
+    snippet: Option<String>,
     task: crate::tasks::Task,
     circle: relm4::Controller<crate::widgets::circle::Model>,
 }
@@ -30,6 +55,186 @@ impl Model {
             date.format("%Y-%m-%d").to_string()
         }
     }
+
+    fn due(&self) -> Option<String> {
+        let due_date = self.task.due_date?;
+        let mut due = format!("due {}", self.date_alias(due_date));
+
+        if let Some(time) = self.task.due_time() {
+            due.push_str(&format!(" at {}", crate::date::format_time(time)));
+        }
+
+        Some(due)
+    }
+
+    fn stale(&self) -> Option<String> {
+        let today = crate::date::today();
+
+        if !crate::tasks::review::is_stale(&self.task, today) {
+            return None;
+        }
+
+        Some(format!("untouched for {} days", self.task.age(today)?))
+    }
+
+    fn add_attachments(&self, widgets: &ModelWidgets) {
+        let attachments = crate::tasks::attachment::list(&self.task);
+
+        widgets.attachments.set_visible(!attachments.is_empty());
+
+        for path in attachments {
+            let name = path
+                .file_name()
+                .map(|x| x.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            let button = gtk::Button::with_label(&name);
+            button.add_css_class("flat");
+            button.connect_clicked(move |_| open(&path));
+
+            widgets.attachments_box.append(&button);
+        }
+    }
+
+    fn save<F: FnOnce(&mut crate::tasks::Task)>(
+        &self,
+        sender: &relm4::ComponentSender<Self>,
+        f: F,
+    ) {
+        let mut task = self.task.clone();
+        f(&mut task);
+
+        sender.output(MsgOutput::Save(Box::new(task))).ok();
+    }
+}
+
+/// Action of a key pressed on a focused task row.
+pub fn shortcut(key: gtk::gdk::Key, modifiers: gtk::gdk::ModifierType) -> Option<MsgInput> {
+    use gtk::gdk::Key;
+
+    if modifiers.intersects(gtk::gdk::ModifierType::CONTROL_MASK | gtk::gdk::ModifierType::ALT_MASK)
+    {
+        return None;
+    }
+
+    let msg = match key.to_lower() {
+        Key::F2 => MsgInput::Edit,
+        Key::Delete | Key::KP_Delete => MsgInput::Delete,
+        Key::a | Key::b | Key::c | Key::d | Key::e => {
+            let priority = key.to_lower().to_unicode()? as u8 - b'a';
+            MsgInput::Priority(priority.into())
+        }
+        Key::_0 => MsgInput::Priority(todo_txt::Priority::default()),
+        Key::t => MsgInput::Defer(chrono::Duration::days(1)),
+        Key::w => MsgInput::Defer(chrono::Duration::weeks(1)),
+        Key::f => MsgInput::Flag,
+        Key::p => MsgInput::Plan,
+        _ => return None,
+    };
+
+    Some(msg)
+}
+
+/// Opens a file with the default application.
+pub fn open(path: &std::path::Path) {
+    let launcher = gtk::FileLauncher::new(Some(&gtk::gio::File::for_path(path)));
+    let path = path.to_path_buf();
+
+    launcher.launch(
+        None::<&gtk::Window>,
+        None::<&gtk::gio::Cancellable>,
+        move |result| {
+            if let Err(err) = result {
+                log::error!("Unable to open {}: {err}", path.display());
+            }
+        },
+    );
+}
+
+/// Accepts files dropped from a file manager.
+pub fn file_drop_target<F: Fn(Vec<std::path::PathBuf>) + 'static>(f: F) -> gtk::DropTarget {
+    let target = gtk::DropTarget::new(
+        gtk::gdk::FileList::static_type(),
+        gtk::gdk::DragAction::COPY,
+    );
+
+    target.connect_drop(move |_, value, _, _| {
+        let Ok(files) = value.get::<gtk::gdk::FileList>() else {
+            return false;
+        };
+
+        let paths = files
+            .files()
+            .iter()
+            .filter_map(|x| x.path())
+            .collect::<Vec<_>>();
+
+        f(paths);
+
+        true
+    });
+
+    target
+}
+
+/// Lets the task `id` be dragged from `widget`.
+pub fn drag_source(widget: &gtk::Widget, task: &crate::tasks::Task) -> gtk::DragSource {
+    let dragged = Dragged {
+        id: task.id,
+        line: task.to_string(),
+    };
+
+    let source = gtk::DragSource::new();
+    source.set_actions(gtk::gdk::DragAction::COPY);
+    source.set_content(Some(&gtk::gdk::ContentProvider::for_value(
+        &dragged.to_value(),
+    )));
+
+    let paintable = gtk::WidgetPaintable::new(Some(widget));
+    source.connect_drag_begin(move |source, _| source.set_icon(Some(&paintable), 0, 0));
+
+    source
+}
+
+/// Drag payload of a task row.
+#[derive(Clone, Debug, glib::Boxed)]
+#[boxed_type(name = "EffitaskDraggedTask")]
+pub struct Dragged {
+    id: usize,
+    /// The task line when the drag started, to recognize it on drop.
+    line: String,
+}
+
+impl Dragged {
+    /// Id of the dragged task, if it's still at this place in the list.
+    fn id(&self) -> Option<usize> {
+        let list = crate::application::tasks();
+        let task = list.tasks.get(self.id)?;
+
+        (task.to_string() == self.line).then_some(self.id)
+    }
+}
+
+/// Accepts a task dragged with [`drag_source`], `f` receives its id, the drop
+/// position and the keyboard modifiers.
+///
+/// Drops of tasks missing from the list, like the import preview ones or
+/// tasks changed since the drag started, are refused.
+pub fn task_drop_target<F: Fn(usize, f64, f64, gtk::gdk::ModifierType) -> bool + 'static>(
+    f: F,
+) -> gtk::DropTarget {
+    let target = gtk::DropTarget::new(Dragged::static_type(), gtk::gdk::DragAction::COPY);
+
+    target.connect_drop(move |target, value, x, y| {
+        let Some(id) = value.get::<Dragged>().ok().and_then(|x| x.id()) else {
+            log::warn!("Dropped task not found in the list");
+            return false;
+        };
+
+        f(id, x, y, target.current_event_state())
+    });
+
+    target
 }
 
 #[relm4::component(pub)]
@@ -51,7 +256,14 @@ impl relm4::SimpleComponent for Model {
             .launch(init.clone())
             .detach();
 
-        let model = Self { task: init, circle };
+        let mut model = Self {
+            editing: false,
+            controllers: Vec::new(),
+            read_only: false,
+            snippet: None,
+            task: init,
+            circle,
+        };
 
         let widgets = view_output!();
 
@@ -63,13 +275,50 @@ impl relm4::SimpleComponent for Model {
             }
         }
 
+        model.add_attachments(&widgets);
+
         let gesture = gtk::GestureClick::new();
-        gesture.connect_pressed(move |_, n_press, _, _| {
-            if n_press == 2 {
-                sender.input(MsgInput::Click);
+        gesture.connect_pressed(gtk::glib::clone!(
+            #[strong]
+            sender,
+            move |_, n_press, _, _| {
+                if n_press == 2 {
+                    sender.input(MsgInput::Click);
+                }
             }
+        ));
+        root.add_controller(gesture.clone());
+
+        let subject = model.task.subject.clone();
+        widgets.subject_entry.connect_map(move |entry| {
+            entry.set_text(&subject);
+            entry.grab_focus();
         });
-        root.add_controller(gesture);
+
+        let key = gtk::EventControllerKey::new();
+        key.connect_key_pressed(gtk::glib::clone!(
+            #[strong]
+            sender,
+            move |_, key, _, _| {
+                if key == gtk::gdk::Key::Escape {
+                    sender.input(MsgInput::Cancel);
+                    gtk::glib::Propagation::Stop
+                } else {
+                    gtk::glib::Propagation::Proceed
+                }
+            }
+        ));
+        widgets.subject_entry.add_controller(key);
+
+        let drag = drag_source(root.upcast_ref(), &model.task);
+        root.add_controller(drag.clone());
+
+        let drop = file_drop_target(move |files| {
+            sender.input(MsgInput::Attach(files));
+        });
+        root.add_controller(drop.clone());
+
+        model.controllers = vec![gesture.upcast(), drag.upcast(), drop.upcast()];
 
         if !model.task.priority.is_lowest() {
             let priority = (b'a' + u8::from(model.task.priority.clone())) as char;
@@ -80,14 +329,79 @@ impl relm4::SimpleComponent for Model {
     }
 
     fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
+        if self.read_only && !matches!(msg, MsgInput::Snippet(_)) {
+            return;
+        }
+
         match msg {
-            MsgInput::Toggle => sender
-                .output(MsgOutput::Complete(Box::new(self.task.clone())))
-                .ok(),
-            MsgInput::Click => sender
-                .output(MsgOutput::Edit(Box::new(self.task.clone())))
-                .ok(),
-        };
+            MsgInput::Attach(files) => {
+                sender
+                    .output(MsgOutput::Attach(Box::new(self.task.clone()), files))
+                    .ok();
+            }
+            MsgInput::Toggle => {
+                sender
+                    .output(MsgOutput::Complete(Box::new(self.task.clone())))
+                    .ok();
+            }
+            MsgInput::Click => {
+                sender
+                    .output(MsgOutput::Edit(Box::new(self.task.clone())))
+                    .ok();
+            }
+            MsgInput::Cancel => self.editing = false,
+            MsgInput::Defer(duration) => self.save(&sender, |task| {
+                let today = crate::date::today();
+                let threshold = task.threshold_date.filter(|x| *x > today).unwrap_or(today);
+
+                task.threshold_date = Some(threshold + duration);
+            }),
+            MsgInput::Delete => {
+                sender
+                    .output(MsgOutput::Delete(vec![self.task.clone()]))
+                    .ok();
+            }
+            MsgInput::Edit => self.editing = true,
+            MsgInput::Flag => self.save(&sender, |task| task.flagged = !task.flagged),
+            MsgInput::Plan => {
+                let today = crate::date::today();
+                let date = if self.task.plan().is_some_and(|(date, _)| date == today) {
+                    None
+                } else {
+                    Some(today)
+                };
+
+                sender
+                    .output(MsgOutput::Bulk(
+                        vec![self.task.id],
+                        crate::tasks::bulk::Action::Plan(date),
+                    ))
+                    .ok();
+            }
+            MsgInput::Priority(priority) => self.save(&sender, |task| task.priority = priority),
+            MsgInput::ReadOnly => {
+                self.read_only = true;
+
+                for controller in &self.controllers {
+                    controller.set_propagation_phase(gtk::PropagationPhase::None);
+                }
+            }
+            MsgInput::Rename(subject) => {
+                self.editing = false;
+
+                let subject = subject.trim();
+
+                if !subject.is_empty() && subject != self.task.subject {
+                    self.save(&sender, |task| task.subject = subject.to_string());
+                }
+            }
+            MsgInput::Snippet(snippet) => self.snippet = Some(snippet),
+            MsgInput::Timer => {
+                sender
+                    .output(MsgOutput::Timer(Box::new(self.task.clone())))
+                    .ok();
+            }
+        }
     }
 
     view! {
@@ -106,13 +420,36 @@ impl relm4::SimpleComponent for Model {
 
                     gtk::CheckButton {
                         set_active: model.task.finished,
+                        #[watch]
+                        set_sensitive: !model.read_only,
 
                         connect_toggled => MsgInput::Toggle,
                     },
                     gtk::Label {
                         set_markup: model.task.markup_subject().as_str(),
                         set_xalign: 0.,
+                        #[watch]
+                        set_visible: !model.editing,
                     },
+                    #[name = "subject_entry"]
+                    gtk::Entry {
+                        set_hexpand: true,
+                        #[watch]
+                        set_visible: model.editing,
+
+                        connect_activate[sender] => move |entry| {
+                            sender.input(MsgInput::Rename(entry.text().to_string()));
+                        },
+                    },
+                },
+                gtk::Label {
+                    add_css_class: "snippet",
+                    set_wrap: true,
+                    set_xalign: 0.,
+                    #[watch]
+                    set_markup: model.snippet.as_deref().unwrap_or_default(),
+                    #[watch]
+                    set_visible: model.snippet.is_some(),
                 },
                 gtk::Box {
                     set_orientation: gtk::Orientation::Horizontal,
@@ -131,18 +468,56 @@ impl relm4::SimpleComponent for Model {
                             },
                         },
                     },
+                    #[name="attachments"]
+                    gtk::MenuButton {
+                        set_icon_name: "folder-documents",
+                        set_tooltip_text: Some("Attachments"),
+
+                        #[wrap(Some)]
+                        set_popover = &gtk::Popover {
+                            set_position: gtk::PositionType::Right,
+
+                            #[name="attachments_box"]
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                            },
+                        },
+                    },
+                    gtk::Button {
+                        add_css_class: "flat",
+                        set_icon_name: "media-playback-start",
+                        set_tooltip_text: Some("Start a pomodoro"),
+                        #[watch]
+                        set_visible: !model.task.finished && !model.read_only,
+
+                        connect_clicked => MsgInput::Timer,
+                    },
+                    gtk::Button {
+                        add_css_class: "flat",
+                        set_icon_name: "user-trash",
+                        set_tooltip_text: Some("Delete"),
+                        #[watch]
+                        set_visible: !model.read_only,
+
+                        connect_clicked => MsgInput::Delete,
+                    },
                     #[name="keywords"]
                     gtk::Box {
-                        set_visible: !model.task.tags.is_empty(),
+                        set_visible: !model.task.keywords().is_empty(),
 
                         gtk::Image {
                             set_icon_name: Some("mail-attachment"),
                         },
                         #[name="keywords_label"]
                         gtk::Label {
-                            set_text: &model.task.tags.iter().map(|(k, v)| format!("{k}: {v}")).collect::<Vec<_>>().join(" · "),
+                            set_text: &model.task.keywords().iter().map(|(k, v)| format!("{k}: {v}")).collect::<Vec<_>>().join(" · "),
                         },
                     },
+                    gtk::Label {
+                        add_css_class: "stale",
+                        set_text?: &model.stale(),
+                        set_visible: model.stale().is_some(),
+                    },
                     gtk::Box {
                         add_css_class: "date",
                         set_halign: gtk::Align::End,
@@ -162,9 +537,14 @@ impl relm4::SimpleComponent for Model {
                         #[name="due_label"]
                         gtk::Label {
                             add_css_class: "due",
-                            set_text?: &model.task.due_date.map(|x| format!("due {}", model.date_alias(x))),
+                            set_text?: &model.due(),
                             set_visible: model.task.due_date.is_some(),
                         },
+                        gtk::Label {
+                            add_css_class: "duration",
+                            set_text?: &model.task.duration().map(|x| format!("⏱ {}", crate::date::format_duration(x))),
+                            set_visible: model.task.duration().is_some(),
+                        },
                     },
                 },
             },
@@ -1,19 +1,44 @@
 use adw::prelude::*;
+use gtk::glib;
 
 #[derive(Debug)]
 pub enum MsgInput {
+    Bulk(crate::tasks::bulk::Action),
+    Delete,
+    DisableScroll,
+    Drop(usize, usize),
+    Focus(usize),
+    Highlight(String),
     Map,
     NeedUpdate,
     Outdated,
+    /// Rows only show the tasks, without actions, shortcuts or selection.
+    ReadOnly,
+    /// Rows can be reordered by drag and drop, as the plan of this day.
+    Reorderable(chrono::NaiveDate),
+    SelectionChanged,
+    SortBy(Sort),
     Update(Vec<crate::tasks::Task>),
 }
 
+pub type Sort = fn(&crate::tasks::Task, &crate::tasks::Task) -> std::cmp::Ordering;
+
+type TagAction = fn(String) -> crate::tasks::bulk::Action;
+
 #[derive(Default)]
 pub struct Model {
     children: Vec<relm4::Controller<super::task::Model>>,
+    /// Task ids, in the rows order.
+    ids: Vec<usize>,
     tasks: Vec<crate::tasks::Task>,
     outdated: bool,
     filter: crate::Filter,
+    sort: Option<Sort>,
+    highlight: String,
+    /// Task to focus after the next refresh, to chain keyboard actions.
+    focus: Option<usize>,
+    plan: Option<chrono::NaiveDate>,
+    read_only: bool,
 }
 
 impl Model {
@@ -37,18 +62,296 @@ impl Model {
         widgets.nothing.set_visible(false);
 
         let mut sorted_tasks = self.tasks.clone();
-        sorted_tasks.sort();
-        sorted_tasks.reverse();
+
+        if let Some(sort) = self.sort {
+            sorted_tasks.sort_by(sort);
+        } else {
+            sorted_tasks.sort();
+            sorted_tasks.reverse();
+        }
 
         for task in &sorted_tasks {
-            let child = super::task::Model::builder()
-                .launch(task.clone())
-                .forward(sender.output_sender(), std::convert::identity);
+            let builder = super::task::Model::builder().launch(task.clone());
+            let child = if self.read_only {
+                let child = builder.detach();
+                child.emit(super::task::MsgInput::ReadOnly);
+                child
+            } else {
+                builder.forward(sender.output_sender(), std::convert::identity)
+            };
+
+            if let Some(snippet) = crate::tasks::index::snippet(task, &self.highlight) {
+                child.emit(super::task::MsgInput::Snippet(snippet));
+            }
 
             widgets.list_box.append(child.widget());
 
+            if let Some(row) = child.widget().parent() {
+                if !self.read_only {
+                    row.add_controller(Self::shortcuts(task.id, child.sender(), sender));
+                }
+
+                if self.focus == Some(task.id) {
+                    self.focus = None;
+                    row.grab_focus();
+                }
+            }
+
             self.children.push(child);
+            self.ids.push(task.id);
+        }
+    }
+
+    fn selected(&self, widgets: &ModelWidgets) -> Vec<usize> {
+        widgets
+            .list_box
+            .selected_rows()
+            .iter()
+            .filter_map(|row| self.ids.get(row.index() as usize).copied())
+            .collect()
+    }
+
+    fn selection_changed(&self, widgets: &ModelWidgets) {
+        let count = self.selected(widgets).len();
+
+        widgets.selection_bar.set_revealed(count > 1);
+        widgets
+            .selection_label
+            .set_text(&format!("{count} tasks selected"));
+    }
+
+    fn bulk(
+        &self,
+        widgets: &ModelWidgets,
+        sender: &relm4::ComponentSender<Self>,
+        action: crate::tasks::bulk::Action,
+    ) {
+        let ids = self.selected(widgets);
+
+        if ids.is_empty() {
+            return;
         }
+
+        sender
+            .output(super::task::MsgOutput::Bulk(ids, action))
+            .ok();
+    }
+
+    fn reorderable(
+        &mut self,
+        widgets: &ModelWidgets,
+        sender: &relm4::ComponentSender<Self>,
+        date: chrono::NaiveDate,
+    ) {
+        if self.plan.replace(date).is_some() {
+            return;
+        }
+
+        let list_box = widgets.list_box.clone();
+        let sender = sender.clone();
+
+        widgets
+            .list_box
+            .add_controller(super::task::task_drop_target(move |id, _, y, _| {
+                let index = list_box
+                    .row_at_y(y as i32)
+                    .map(|row| row.index() as usize)
+                    .unwrap_or(usize::MAX);
+
+                sender.input(MsgInput::Drop(id, index));
+                true
+            }));
+    }
+
+    /// Moves the dropped task at `index` and saves the new plan order.
+    ///
+    /// Only the unfinished tasks planned for this day are re-ranked, with the
+    /// dropped one: the tasks left over from the previous days keep their plan.
+    fn drop_task(&self, sender: &relm4::ComponentSender<Self>, id: usize, index: usize) {
+        let Some(date) = self.plan else {
+            return;
+        };
+
+        let planned = |x: &usize| {
+            self.tasks.iter().any(|task| {
+                task.id == *x && !task.finished && task.plan().is_some_and(|(d, _)| d == date)
+            })
+        };
+        let position = self
+            .ids
+            .iter()
+            .take(index)
+            .filter(|x| **x != id && planned(x))
+            .count();
+
+        let mut ids = self
+            .ids
+            .iter()
+            .copied()
+            .filter(|x| *x != id && planned(x))
+            .collect::<Vec<_>>();
+        ids.insert(position, id);
+
+        sender
+            .output(super::task::MsgOutput::Bulk(
+                ids,
+                crate::tasks::bulk::Action::Plan(Some(date)),
+            ))
+            .ok();
+    }
+
+    fn delete(&self, widgets: &ModelWidgets, sender: &relm4::ComponentSender<Self>) {
+        let ids = self.selected(widgets);
+        let tasks = self
+            .tasks
+            .iter()
+            .filter(|x| ids.contains(&x.id))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !tasks.is_empty() {
+            sender.output(super::task::MsgOutput::Delete(tasks)).ok();
+        }
+    }
+
+    fn date_popover(
+        sender: &relm4::ComponentSender<Self>,
+        action: fn(Option<chrono::NaiveDate>) -> crate::tasks::bulk::Action,
+    ) -> gtk::Popover {
+        let popover = gtk::Popover::new();
+        let vbox = gtk::Box::new(gtk::Orientation::Vertical, 5);
+
+        let calendar = gtk::Calendar::new();
+        calendar.connect_day_selected(glib::clone!(
+            #[strong]
+            sender,
+            #[weak]
+            popover,
+            move |calendar| {
+                popover.popdown();
+                sender.input(MsgInput::Bulk(action(Some(crate::date::from_glib(
+                    calendar.date(),
+                )))));
+            }
+        ));
+        vbox.append(&calendar);
+
+        let clear = gtk::Button::with_label("Clear");
+        clear.connect_clicked(glib::clone!(
+            #[strong]
+            sender,
+            #[weak]
+            popover,
+            move |_| {
+                popover.popdown();
+                sender.input(MsgInput::Bulk(action(None)));
+            }
+        ));
+        vbox.append(&clear);
+
+        popover.set_child(Some(&vbox));
+
+        popover
+    }
+
+    fn tag_popover(
+        sender: &relm4::ComponentSender<Self>,
+        placeholder: &str,
+        actions: &[(&str, TagAction)],
+    ) -> gtk::Popover {
+        let popover = gtk::Popover::new();
+        let vbox = gtk::Box::new(gtk::Orientation::Vertical, 5);
+
+        let entry = gtk::Entry::new();
+        entry.set_placeholder_text(Some(placeholder));
+        vbox.append(&entry);
+
+        let buttons = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        buttons.add_css_class("linked");
+
+        for (label, action) in actions {
+            let action = *action;
+            let button = gtk::Button::with_label(label);
+
+            button.connect_clicked(glib::clone!(
+                #[strong]
+                sender,
+                #[weak]
+                popover,
+                #[weak]
+                entry,
+                move |_| {
+                    popover.popdown();
+                    sender.input(MsgInput::Bulk(action(entry.text().to_string())));
+                    entry.set_text("");
+                }
+            ));
+            buttons.append(&button);
+        }
+        vbox.append(&buttons);
+
+        popover.set_child(Some(&vbox));
+
+        popover
+    }
+
+    fn priority_popover(sender: &relm4::ComponentSender<Self>) -> gtk::Popover {
+        let popover = gtk::Popover::new();
+        let buttons = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        buttons.add_css_class("linked");
+
+        for priority in (0..5)
+            .map(todo_txt::Priority::from)
+            .chain([todo_txt::Priority::default()])
+        {
+            let label = if priority.is_lowest() {
+                "None".to_string()
+            } else {
+                priority.to_string()
+            };
+            let button = gtk::Button::with_label(&label);
+
+            button.connect_clicked(glib::clone!(
+                #[strong]
+                sender,
+                #[weak]
+                popover,
+                move |_| {
+                    popover.popdown();
+                    sender.input(MsgInput::Bulk(crate::tasks::bulk::Action::Priority(
+                        priority.clone(),
+                    )));
+                }
+            ));
+            buttons.append(&button);
+        }
+
+        popover.set_child(Some(&buttons));
+
+        popover
+    }
+
+    fn shortcuts(
+        id: usize,
+        task: &relm4::Sender<super::task::MsgInput>,
+        sender: &relm4::ComponentSender<Self>,
+    ) -> gtk::EventControllerKey {
+        let key = gtk::EventControllerKey::new();
+        let task = task.clone();
+        let sender = sender.clone();
+
+        key.connect_key_pressed(move |_, key, _, modifiers| {
+            match super::task::shortcut(key, modifiers) {
+                Some(msg) => {
+                    sender.input(MsgInput::Focus(id));
+                    task.emit(msg);
+                    gtk::glib::Propagation::Stop
+                }
+                None => gtk::glib::Propagation::Proceed,
+            }
+        });
+
+        key
     }
 
     fn outdated(&mut self, widgets: &ModelWidgets) {
@@ -63,7 +366,9 @@ impl Model {
         use relm4::RelmRemoveAllExt as _;
 
         widgets.list_box.remove_all();
+        widgets.selection_bar.set_revealed(false);
         self.children = Vec::new();
+        self.ids = Vec::new();
     }
 }
 
@@ -85,6 +390,29 @@ impl relm4::Component for Model {
             ..Default::default()
         };
 
+        use crate::tasks::bulk::Action;
+
+        let priority_popover = Self::priority_popover(&sender);
+        let project_popover = Self::tag_popover(
+            &sender,
+            "Project",
+            &[
+                ("Add", Action::AddProject),
+                ("Remove", Action::RemoveProject),
+                ("Move to", Action::Move),
+            ],
+        );
+        let context_popover = Self::tag_popover(
+            &sender,
+            "Context",
+            &[
+                ("Add", Action::AddContext),
+                ("Remove", Action::RemoveContext),
+            ],
+        );
+        let defer_popover = Self::date_popover(&sender, Action::Defer);
+        let due_popover = Self::date_popover(&sender, Action::Due);
+
         let widgets = view_output!();
         sender.input(MsgInput::Outdated);
 
@@ -101,8 +429,25 @@ impl relm4::Component for Model {
         use MsgInput::*;
 
         match msg {
+            Bulk(action) => self.bulk(widgets, &sender, action),
+            Delete => self.delete(widgets, &sender),
+            DisableScroll => widgets
+                .scrolled_window
+                .set_vscrollbar_policy(gtk::PolicyType::Never),
+            Drop(id, index) => self.drop_task(&sender, id, index),
+            Focus(id) => self.focus = Some(id),
+            Highlight(query) => self.highlight = query,
             Outdated => self.outdated(widgets),
+            ReadOnly => {
+                self.read_only = true;
+                widgets
+                    .list_box
+                    .set_selection_mode(gtk::SelectionMode::None);
+            }
+            Reorderable(date) => self.reorderable(widgets, &sender, date),
+            SelectionChanged => self.selection_changed(widgets),
             Map => self.map(widgets, &sender),
+            SortBy(sort) => self.sort = Some(sort),
             NeedUpdate => {
                 self.tasks = (self.filter)();
 
@@ -121,21 +466,81 @@ impl relm4::Component for Model {
     }
 
     view! {
-        gtk::ScrolledWindow {
-            gtk::Box {
-                #[name = "list_box"]
-                gtk::ListBox {
-                    set_hexpand: true,
-                    set_vexpand: true,
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+
+            #[name = "scrolled_window"]
+            gtk::ScrolledWindow {
+                set_vexpand: true,
+
+                gtk::Box {
+                    #[name = "list_box"]
+                    gtk::ListBox {
+                        set_hexpand: true,
+                        set_selection_mode: gtk::SelectionMode::Multiple,
+                        set_vexpand: true,
+
+                        connect_selected_rows_changed => MsgInput::SelectionChanged,
+                    },
+                    #[name = "nothing"]
+                    gtk::Label {
+                        set_hexpand: true,
+                        set_text: "Nothing to do :)",
+                        set_vexpand: true,
+                    },
+                    #[name = "outdated"]
+                    gtk::Spinner {
+                    },
+                },
+            },
+            #[name = "selection_bar"]
+            gtk::ActionBar {
+                set_revealed: false,
+
+                #[name = "selection_label"]
+                pack_start = &gtk::Label {
+                },
+                pack_start = &gtk::Button {
+                    set_label: "Complete",
+                    connect_clicked => MsgInput::Bulk(crate::tasks::bulk::Action::Complete),
+                },
+                pack_start = &gtk::MenuButton {
+                    set_label: "Priority",
+                    set_popover: Some(&priority_popover),
+                },
+                pack_start = &gtk::MenuButton {
+                    set_label: "Project",
+                    set_popover: Some(&project_popover),
+                },
+                pack_start = &gtk::MenuButton {
+                    set_label: "Context",
+                    set_popover: Some(&context_popover),
+                },
+                pack_start = &gtk::MenuButton {
+                    set_label: "Defer",
+                    set_popover: Some(&defer_popover),
+                },
+                pack_start = &gtk::MenuButton {
+                    set_label: "Due",
+                    set_popover: Some(&due_popover),
+                },
+                pack_start = &gtk::Button {
+                    set_label: "Flag",
+                    connect_clicked => MsgInput::Bulk(crate::tasks::bulk::Action::Flag(true)),
+                },
+                pack_start = &gtk::Button {
+                    set_label: "Unflag",
+                    connect_clicked => MsgInput::Bulk(crate::tasks::bulk::Action::Flag(false)),
                 },
-                #[name = "nothing"]
-                gtk::Label {
-                    set_hexpand: true,
-                    set_text: "Nothing to do :)",
-                    set_vexpand: true,
+                pack_start = &gtk::Button {
+                    set_label: "Today",
+                    set_tooltip_text: Some("Add to the today plan"),
+                    connect_clicked => MsgInput::Bulk(crate::tasks::bulk::Action::Plan(Some(crate::date::today()))),
                 },
-                #[name = "outdated"]
-                gtk::Spinner {
+                pack_end = &gtk::Button {
+                    add_css_class: "destructive-action",
+                    set_label: "Delete",
+                    connect_clicked => MsgInput::Delete,
                 },
             },
 